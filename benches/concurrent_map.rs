@@ -0,0 +1,97 @@
+use criterion::measurement::WallTime;
+use criterion::{criterion_group, criterion_main, BenchmarkGroup, Criterion, black_box};
+use rustc_hash::FxHashMap;
+use static_id::map::IdMap;
+use static_id::static_id::*;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Barrier, Mutex};
+use std::time::Instant;
+
+const THREADS: usize = 4;
+const OPS_PER_THREAD: usize = 10_000;
+
+fn ids() -> Vec<StaticId> {
+    (0..1024).map(|i| StaticId::from_str(&format!("ID{}", i), "VENUE")).collect()
+}
+
+/// Runs `work(t)` on each of `THREADS` threads, created once for the
+/// benchmark, and times only the work between two barriers.
+fn bench_threads<F>(group: &mut BenchmarkGroup<'_, WallTime>, name: &str, work: F)
+where
+    F: Fn(usize) + Sync,
+{
+    let start = Barrier::new(THREADS + 1);
+    let done = Barrier::new(THREADS + 1);
+    let iters = AtomicU64::new(0);
+    let stop = AtomicBool::new(false);
+
+    std::thread::scope(|scope| {
+        for t in 0..THREADS {
+            let (start, done, iters, stop, work) = (&start, &done, &iters, &stop, &work);
+            scope.spawn(move || loop {
+                start.wait();
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                for _ in 0..iters.load(Ordering::Relaxed) {
+                    work(t);
+                }
+                done.wait();
+            });
+        }
+
+        group.bench_function(name, |b| b.iter_custom(|n| {
+            iters.store(n, Ordering::Relaxed);
+            let begin = Instant::now();
+            start.wait();
+            done.wait();
+            begin.elapsed()
+        }));
+
+        stop.store(true, Ordering::Relaxed);
+        start.wait();
+    });
+}
+
+/// Every thread mixes 9 reads with 1 write over its own stride of the ids.
+fn concurrent_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent_map");
+    let ids = ids();
+
+    let id_map = IdMap::new();
+    let mutex_map = Mutex::new(FxHashMap::default());
+    for (i, &id) in ids.iter().enumerate() {
+        id_map.insert(id, i);
+        mutex_map.lock().unwrap().insert(id, i);
+    }
+
+    bench_threads(&mut group, "IdMap", |t| {
+        for op in 0..OPS_PER_THREAD {
+            let id = ids[(op * THREADS + t) % ids.len()];
+            if op % 10 == 0 {
+                id_map.insert(id, op);
+            } else {
+                black_box(id_map.get(&id));
+            }
+        }
+    });
+
+    bench_threads(&mut group, "Mutex<FxHashMap>", |t| {
+        for op in 0..OPS_PER_THREAD {
+            let id = ids[(op * THREADS + t) % ids.len()];
+            if op % 10 == 0 {
+                mutex_map.lock().unwrap().insert(id, op);
+            } else {
+                black_box(mutex_map.lock().unwrap().get(&id).copied());
+            }
+        }
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    concurrent_map,
+);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, black_box};
+use static_id::static_id::*;
+
+fn intern_hit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intern_hit");
+    let _ = StaticId::from_str("AAPL", "NASDAQ");
+    let _ = StaticId::from_str("KR7005930003", "KRX");
+
+    group.bench_function("StaticId::from_str short", |b| b.iter(|| {
+        black_box(StaticId::from_str(black_box("AAPL"), black_box("NASDAQ")))
+    }));
+
+    group.bench_function("StaticId::from_str long", |b| b.iter(|| {
+        black_box(StaticId::from_str(black_box("KR7005930003"), black_box("KRX")))
+    }));
+
+    group.bench_function("StaticId::lookup", |b| b.iter(|| {
+        black_box(StaticId::lookup(black_box("AAPL"), black_box("NASDAQ")))
+    }));
+
+    group.finish();
+}
+
+fn intern_miss(c: &mut Criterion) {
+    let mut group = c.benchmark_group("intern_miss");
+    let mut counter = 0u64;
+
+    // every iteration interns a code never seen before
+    group.bench_function("StaticId::from_str", |b| b.iter_batched(
+        || {
+            counter += 1;
+            format!("MISS{}", counter)
+        },
+        |code| black_box(StaticId::from_str(&code, "NASDAQ")),
+        BatchSize::SmallInput,
+    ));
+
+    group.bench_function("StaticId::lookup", |b| b.iter(|| {
+        black_box(StaticId::lookup(black_box("NEVER"), black_box("NASDAQ")))
+    }));
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    intern_hit,
+    intern_miss,
+);
+criterion_main!(benches);
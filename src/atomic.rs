@@ -0,0 +1,142 @@
+//! A cell holding a `StaticIdNxM` that threads can read and replace without a lock.
+//!
+//! ```rust
+//! use static_id::atomic::AtomicStaticId;
+//! use static_id::StaticId;
+//! use std::sync::atomic::Ordering;
+//!
+//! let december = StaticId::from_str("ESZ4", "CME");
+//! let march = StaticId::from_str("ESH5", "CME");
+//!
+//! let front_month = AtomicStaticId::new(december);
+//! assert_eq!(front_month.load(Ordering::Acquire), december);
+//! assert_eq!(front_month.swap(march, Ordering::AcqRel), december);
+//! assert_eq!(
+//!     front_month.compare_exchange(december, march, Ordering::AcqRel, Ordering::Acquire),
+//!     Err(march)
+//! );
+//! ```
+use crate::id_cache::HasIdCache;
+use crate::interned::Interned;
+use crate::static_id::{IdEntryNxM, StaticIdNxM};
+use std::fmt;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+/// A `StaticIdNxM` that can be shared between threads, backed by an `AtomicPtr`
+/// to the interned entry. Since ids are interned, comparing pointers in
+/// `compare_exchange` compares ids.
+pub struct AtomicStaticIdNxM<const N: usize, const M: usize> {
+    id_ptr: AtomicPtr<IdEntryNxM<N, M>>,
+}
+
+impl<const N: usize, const M: usize> AtomicStaticIdNxM<N, M> {
+    #[inline]
+    fn to_ptr(id: StaticIdNxM<N, M>) -> *mut IdEntryNxM<N, M> {
+        Interned::as_ptr(id.id_ptr) as *mut IdEntryNxM<N, M>
+    }
+
+    #[inline]
+    fn from_ptr(ptr: *mut IdEntryNxM<N, M>) -> StaticIdNxM<N, M> {
+        // Safety: the cell only ever holds pointers to leaked, interned entries
+        StaticIdNxM { id_ptr: Interned::from_static(unsafe { &*ptr }) }
+    }
+
+    #[inline]
+    pub const fn new(id: StaticIdNxM<N, M>) -> Self {
+        Self {
+            id_ptr: AtomicPtr::new(Interned::as_ptr(id.id_ptr) as *mut IdEntryNxM<N, M>),
+        }
+    }
+
+    #[inline]
+    pub fn load(&self, order: Ordering) -> StaticIdNxM<N, M> {
+        Self::from_ptr(self.id_ptr.load(order))
+    }
+
+    #[inline]
+    pub fn store(&self, id: StaticIdNxM<N, M>, order: Ordering) {
+        self.id_ptr.store(Self::to_ptr(id), order);
+    }
+
+    /// Stores `id` and returns the previous id.
+    #[inline]
+    pub fn swap(&self, id: StaticIdNxM<N, M>, order: Ordering) -> StaticIdNxM<N, M> {
+        Self::from_ptr(self.id_ptr.swap(Self::to_ptr(id), order))
+    }
+
+    /// Stores `new` if the cell holds `current`. Returns the previous id,
+    /// in `Ok` if it was `current` and in `Err` otherwise.
+    #[inline]
+    pub fn compare_exchange(
+        &self,
+        current: StaticIdNxM<N, M>,
+        new: StaticIdNxM<N, M>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<StaticIdNxM<N, M>, StaticIdNxM<N, M>> {
+        self.id_ptr
+            .compare_exchange(Self::to_ptr(current), Self::to_ptr(new), success, failure)
+            .map(Self::from_ptr)
+            .map_err(Self::from_ptr)
+    }
+
+    /// Like [`Self::compare_exchange`], but may fail spuriously, which allows
+    /// more efficient code on some platforms when called in a loop.
+    #[inline]
+    pub fn compare_exchange_weak(
+        &self,
+        current: StaticIdNxM<N, M>,
+        new: StaticIdNxM<N, M>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<StaticIdNxM<N, M>, StaticIdNxM<N, M>> {
+        self.id_ptr
+            .compare_exchange_weak(Self::to_ptr(current), Self::to_ptr(new), success, failure)
+            .map(Self::from_ptr)
+            .map_err(Self::from_ptr)
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> StaticIdNxM<N, M> {
+        Self::from_ptr(self.id_ptr.into_inner())
+    }
+}
+
+impl<const N: usize, const M: usize> From<StaticIdNxM<N, M>> for AtomicStaticIdNxM<N, M> {
+    #[inline]
+    fn from(id: StaticIdNxM<N, M>) -> Self {
+        Self::new(id)
+    }
+}
+
+impl<const N: usize, const M: usize> Default for AtomicStaticIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: Default,
+{
+    fn default() -> Self {
+        Self::new(StaticIdNxM::default())
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Debug for AtomicStaticIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.load(Ordering::Relaxed), f)
+    }
+}
+
+pub type AtomicStaticId16x0 = AtomicStaticIdNxM<16, 0>;
+pub type AtomicStaticId16x16 = AtomicStaticIdNxM<16, 16>;
+pub type AtomicStaticId16x32 = AtomicStaticIdNxM<16, 32>;
+pub type AtomicStaticId16x64 = AtomicStaticIdNxM<16, 64>;
+pub type AtomicStaticId32x0 = AtomicStaticIdNxM<32, 0>;
+pub type AtomicStaticId32x16 = AtomicStaticIdNxM<32, 16>;
+pub type AtomicStaticId32x32 = AtomicStaticIdNxM<32, 32>;
+pub type AtomicStaticId32x64 = AtomicStaticIdNxM<32, 64>;
+pub type AtomicStaticId64x0 = AtomicStaticIdNxM<64, 0>;
+pub type AtomicStaticId64x16 = AtomicStaticIdNxM<64, 16>;
+pub type AtomicStaticId64x32 = AtomicStaticIdNxM<64, 32>;
+pub type AtomicStaticId64x64 = AtomicStaticIdNxM<64, 64>;
+pub type AtomicStaticId = AtomicStaticId32x32;
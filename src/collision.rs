@@ -0,0 +1,139 @@
+//! Detection of distinct inputs that intern to the same id.
+//!
+//! With the default [`Truncation`](crate::Truncation) policy, two codes
+//! longer than `N` that share their first `N` bytes become the same id. When
+//! collision detection is on, the intern cache of a type remembers the
+//! normalized, untruncated input each id was first interned from, and
+//! whenever a different input maps to the same id, it records a
+//! [`Collision`] and, depending on the mode, also logs it or panics.
+//!
+//! Detection costs a map lookup per intern and a copy of every distinct
+//! input, so it is meant for tests and debug runs. Ids interned before it is
+//! turned on are remembered from the next input that maps to them.
+//!
+//! ```rust
+//! use static_id::collision::CollisionDetection;
+//! use static_id::StaticId16x32;
+//!
+//! StaticId16x32::set_collision_detection(CollisionDetection::Record);
+//! let a = StaticId16x32::from_str("SPXW  241220P05000000", "CBOE");
+//! let b = StaticId16x32::from_str("SPXW  241220P05000001", "CBOE");
+//! assert_eq!(a, b);
+//!
+//! let collisions = StaticId16x32::collisions();
+//! assert_eq!(collisions.len(), 1);
+//! assert_eq!(collisions[0].first.0, "SPXW  241220P05000000");
+//! assert_eq!(collisions[0].input.0, "SPXW  241220P05000001");
+//! assert_eq!(collisions[0].code, "SPXW  241220P050");
+//! ```
+use rustc_hash::FxHashMap;
+use std::fmt;
+
+/// What the intern cache of a type does about collisions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CollisionDetection {
+    /// Inputs are not remembered. This is the default.
+    #[default]
+    Off,
+    /// Collisions are recorded in the report of the type.
+    Record,
+    /// Collisions are recorded and written to stderr.
+    Log,
+    /// Collisions are recorded, then the intern panics. The id is interned
+    /// before the panic and the cache stays usable.
+    Panic,
+}
+
+/// Two distinct inputs that intern to the same id. The strings are lossy
+/// UTF-8 conversions of the inputs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Collision {
+    /// The stored code of the id.
+    pub code: String,
+    /// The stored venue of the id.
+    pub venue: String,
+    /// The `(code, venue)` input the id was first interned from.
+    pub first: (String, String),
+    /// The different `(code, venue)` input that mapped to the same id.
+    pub input: (String, String),
+}
+
+impl fmt::Display for Collision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "inputs ({:?}, {:?}) and ({:?}, {:?}) both intern to ({:?}, {:?})",
+            self.first.0, self.first.1, self.input.0, self.input.1, self.code, self.venue
+        )
+    }
+}
+
+/// A `(code, venue)` input.
+type Input = (Box<[u8]>, Box<[u8]>);
+
+/// The inputs remembered by an intern cache and the collisions seen so far.
+#[derive(Default)]
+pub(crate) struct CollisionTracker {
+    /// the first input of each id, by intern index
+    originals: FxHashMap<u32, Input>,
+    collisions: Vec<Collision>,
+}
+
+impl CollisionTracker {
+    /// Remembers or checks the input of the id at `index`, whose stored text is `stored`.
+    pub(crate) fn observe(&mut self, mode: CollisionDetection, index: u32, stored: (&str, &str), input: (&[u8], &[u8])) {
+        if mode == CollisionDetection::Off {
+            return;
+        }
+        let first = self.originals.entry(index).or_insert_with(|| (input.0.into(), input.1.into()));
+        if (&*first.0, &*first.1) == input {
+            return;
+        }
+        let collision = Collision {
+            code: stored.0.to_string(),
+            venue: stored.1.to_string(),
+            first: (lossy(&first.0), lossy(&first.1)),
+            input: (lossy(input.0), lossy(input.1)),
+        };
+        if self.collisions.contains(&collision) {
+            return;
+        }
+        self.collisions.push(collision.clone());
+        match mode {
+            CollisionDetection::Log => eprintln!("static_id collision: {}", collision),
+            CollisionDetection::Panic => panic!("static_id collision: {}", collision),
+            CollisionDetection::Off | CollisionDetection::Record => {}
+        }
+    }
+
+    #[inline]
+    pub(crate) fn collisions(&self) -> &[Collision] {
+        &self.collisions
+    }
+}
+
+fn lossy(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe() {
+        let mut tracker = CollisionTracker::default();
+        let mode = CollisionDetection::Record;
+        tracker.observe(mode, 0, ("ABCD", "X"), (b"ABCDE", b"X"));
+        tracker.observe(mode, 0, ("ABCD", "X"), (b"ABCDE", b"X"));
+        assert!(tracker.collisions().is_empty());
+
+        tracker.observe(mode, 0, ("ABCD", "X"), (b"ABCD", b"X"));
+        tracker.observe(mode, 0, ("ABCD", "X"), (b"ABCD", b"X"));
+        assert_eq!(tracker.collisions().len(), 1);
+        assert_eq!(tracker.collisions()[0].first, ("ABCDE".to_string(), "X".to_string()));
+
+        tracker.observe(CollisionDetection::Off, 1, ("Z", ""), (b"Z", b""));
+        assert!(!tracker.originals.contains_key(&1));
+    }
+}
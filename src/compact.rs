@@ -0,0 +1,156 @@
+//! 4-byte id handles for cache-dense tables.
+//!
+//! A [`CompactIdNxM`] is the intern index of an id (see `StaticIdNxM::index`)
+//! as a `u32`, half the size of the `StaticIdNxM` pointer. Converting either
+//! way is cheap and takes no lock: a `StaticIdNxM` knows its index, and the
+//! index resolves through the lock-free table of the type.
+//!
+//! Indices are only meaningful within one process, so serde goes through the
+//! `StaticIdNxM` representation rather than the raw index.
+//!
+//! ```rust
+//! use static_id::compact::CompactId;
+//! use static_id::StaticId;
+//!
+//! let id = StaticId::from_str("AAPL", "NASDAQ");
+//! let compact = CompactId::from(id);
+//! assert_eq!(std::mem::size_of_val(&compact), 4);
+//! assert_eq!(compact.code_str(), "AAPL");
+//! assert_eq!(compact.to_string(), "AAPL@NASDAQ");
+//! assert_eq!(compact.to_static_id(), id);
+//! ```
+use crate::id_cache::HasIdCache;
+use crate::interned::Interned;
+use crate::static_id::StaticIdNxM;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// The intern index of an id of `StaticIdNxM<N, M>`, see the [module docs](self).
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct CompactIdNxM<const N: usize, const M: usize> {
+    index: u32,
+}
+
+impl<const N: usize, const M: usize> CompactIdNxM<N, M> {
+    /// The intern index of the id.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+impl<const N: usize, const M: usize> CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    /// The handle of the id interned at `index`, if there is one.
+    #[inline]
+    pub fn from_index(index: u32) -> Option<Self> {
+        StaticIdNxM::<N, M>::from_index(index).map(Self::from)
+    }
+
+    /// Interns `code` and `venue` like `StaticIdNxM::from_str`. Types without
+    /// a venue take `""` as `venue`.
+    #[inline]
+    pub fn from_str(code: &str, venue: &str) -> Self {
+        let id_ptr = StaticIdNxM::<N, M>::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
+        Self { index: id_ptr.index() }
+    }
+
+    #[inline]
+    pub fn to_static_id(&self) -> StaticIdNxM<N, M> {
+        // a handle is only made from an interned id, and entries are never removed
+        StaticIdNxM::from_index(self.index).unwrap()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn code_str(&self) -> &'static str {
+        Interned::as_static(self.to_static_id().id_ptr).code.as_str()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn venue_str(&self) -> &'static str {
+        Interned::as_static(self.to_static_id().id_ptr).venue.as_str()
+    }
+}
+
+impl<const N: usize, const M: usize> From<StaticIdNxM<N, M>> for CompactIdNxM<N, M> {
+    #[inline]
+    fn from(id: StaticIdNxM<N, M>) -> Self {
+        Self { index: id.index() }
+    }
+}
+
+impl<const N: usize, const M: usize> From<CompactIdNxM<N, M>> for StaticIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    #[inline]
+    fn from(id: CompactIdNxM<N, M>) -> Self {
+        id.to_static_id()
+    }
+}
+
+/// Hashes like the `StaticIdNxM` it stands for.
+impl<const N: usize, const M: usize> Hash for CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_static_id().hash(state);
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Display for CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.to_static_id(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Debug for CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.to_static_id(), f)
+    }
+}
+
+impl<const N: usize, const M: usize> Serialize for CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_static_id().serialize(serializer)
+    }
+}
+
+impl<'de, const N: usize, const M: usize> Deserialize<'de> for CompactIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StaticIdNxM::deserialize(deserializer).map(Self::from)
+    }
+}
+
+pub type CompactId16x0 = CompactIdNxM<16, 0>;
+pub type CompactId16x16 = CompactIdNxM<16, 16>;
+pub type CompactId16x32 = CompactIdNxM<16, 32>;
+pub type CompactId16x64 = CompactIdNxM<16, 64>;
+pub type CompactId32x0 = CompactIdNxM<32, 0>;
+pub type CompactId32x16 = CompactIdNxM<32, 16>;
+pub type CompactId32x32 = CompactIdNxM<32, 32>;
+pub type CompactId32x64 = CompactIdNxM<32, 64>;
+pub type CompactId64x0 = CompactIdNxM<64, 0>;
+pub type CompactId64x16 = CompactIdNxM<64, 16>;
+pub type CompactId64x32 = CompactIdNxM<64, 32>;
+pub type CompactId64x64 = CompactIdNxM<64, 64>;
+pub type CompactId = CompactId32x32;
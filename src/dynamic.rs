@@ -0,0 +1,695 @@
+//! Interned ids without length bounds.
+//!
+//! A [`DynamicId`] is an 8-byte handle like `StaticIdNxM`, but its code and
+//! venue are not stored in fixed `N` and `M` byte arrays: the intern cache
+//! copies their text into an arena of large leaked chunks, so each id uses
+//! only the bytes of its text, and nothing is ever truncated.
+//!
+//! `DynamicId` has the constructors, accessors, policies (normalization,
+//! validation, format and serde representation), secondary-index queries
+//! and trait impls of a `StaticIdNxM` type. It has no core of fixed layout,
+//! so `get_id` and the raw core and archive methods do not exist. It has no
+//! `ByIndex` wrapper or `serde_repr` field modules either: compare
+//! [`DynamicId::index`] to order by intern index, and set the serde
+//! representation with [`DynamicId::set_serde_repr`].
+//!
+//! ```rust
+//! use static_id::dynamic::DynamicId;
+//!
+//! let long = "SPXW  241220P05000000.WEEKLY.EXPIRY.AM.SETTLED";
+//! let id = DynamicId::from_str(long, "CBOE");
+//! assert_eq!(id.code_str(), long);
+//! assert_eq!(id, DynamicId::from_str(long, "CBOE"));
+//! assert_ne!(id, DynamicId::from_str(&long[..32], "CBOE"));
+//! assert_eq!(std::mem::size_of::<DynamicId>(), 8);
+//! assert_eq!(id.to_string(), format!("{}@CBOE", long));
+//! ```
+use crate::error::{IdError, IdPart};
+use crate::fingerprint::{fingerprint128, fingerprint64};
+use crate::format::{AtomicIdFormat, IdFormat};
+use crate::interned::{InternSet, Interned};
+use crate::map::IdHasher;
+use crate::normalization::Normalization;
+use crate::search::{IdIndex, IdText};
+use crate::serde_repr::{self, AtomicSerdeRepr, CowStr, SerdeRepr};
+use crate::static_id::hash_parts;
+use crate::validation::Validation;
+use once_cell::sync::Lazy;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use std::hash::{BuildHasherDefault, Hash, Hasher};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// The interned allocation a `DynamicId` points to.
+#[derive(Debug)]
+pub struct DynamicEntry {
+    code: &'static str,
+    venue: &'static str,
+    index: u32,
+    hash: u64,
+    fingerprint: u64,
+    fingerprint128: u128,
+}
+
+impl DynamicEntry {
+    #[inline]
+    pub fn code(&self) -> &'static str {
+        self.code
+    }
+
+    #[inline]
+    pub fn venue(&self) -> &'static str {
+        self.venue
+    }
+
+    /// Position of the entry in intern order, see `IdEntryNxM::index`.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The hash of the code and venue, computed once at intern time.
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// The 64-bit content fingerprint, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The 128-bit content fingerprint, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint128(&self) -> u128 {
+        self.fingerprint128
+    }
+}
+
+impl IdText for DynamicEntry {
+    #[inline]
+    fn code_text(&self) -> &str {
+        self.code
+    }
+
+    #[inline]
+    fn venue_text(&self) -> &str {
+        self.venue
+    }
+}
+
+impl PartialEq for DynamicEntry {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code && self.venue == other.venue
+    }
+}
+
+impl Eq for DynamicEntry {}
+
+impl Hash for DynamicEntry {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+/// Size of an arena chunk. Longer strings get an allocation of their own.
+const CHUNK: usize = 16 * 1024;
+
+/// A bump allocator of leaked string copies.
+#[derive(Default)]
+struct StrArena {
+    free: &'static mut [u8],
+    bytes: usize,
+}
+
+impl StrArena {
+    fn alloc(&mut self, s: &str) -> &'static str {
+        if s.is_empty() {
+            return "";
+        }
+        self.bytes += s.len();
+        if s.len() > CHUNK / 4 {
+            return Box::leak(s.into());
+        }
+        if s.len() > self.free.len() {
+            self.free = vec![0; CHUNK].leak();
+        }
+        let (head, tail) = std::mem::take(&mut self.free).split_at_mut(s.len());
+        head.copy_from_slice(s.as_bytes());
+        self.free = tail;
+        // Safety: `head` holds a copy of the UTF-8 bytes of `s`
+        unsafe { std::str::from_utf8_unchecked(head) }
+    }
+}
+
+/// The intern cache behind `DynamicId`, with the same policies and secondary
+/// indexes as `IdCacheNxM`. There is only the global one, so that every
+/// `DynamicId` is interned in it.
+pub struct DynamicIdCache {
+    /// keyed by the hash precomputed in each entry
+    map: InternSet<DynamicEntry, BuildHasherDefault<IdHasher>>,
+    /// the entries in intern order, and the code and venue indexes
+    index: IdIndex<DynamicEntry>,
+    arena: StrArena,
+    normalization: Normalization,
+    code_validation: Validation,
+    venue_validation: Validation,
+}
+
+impl DynamicIdCache {
+    fn new() -> Self {
+        Self {
+            map: InternSet::default(),
+            index: IdIndex::default(),
+            arena: StrArena::default(),
+            normalization: Normalization::default(),
+            code_validation: Validation::default(),
+            venue_validation: Validation::default(),
+        }
+    }
+
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Sets the normalization policy. Ids interned before the change are not
+    /// re-normalized, so the policy should be set before the first intern.
+    #[inline]
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    #[inline]
+    pub fn code_validation(&self) -> Validation {
+        self.code_validation
+    }
+
+    #[inline]
+    pub fn venue_validation(&self) -> Validation {
+        self.venue_validation
+    }
+
+    /// Sets the validation policies of the code and the venue.
+    #[inline]
+    pub fn set_validation(&mut self, code: Validation, venue: Validation) {
+        self.code_validation = code;
+        self.venue_validation = venue;
+    }
+
+    /// Normalizes `code` and `venue` and interns them.
+    pub fn intern_str(&mut self, code: &str, venue: &str) -> Interned<DynamicEntry> {
+        let code = self.normalization.apply_str(code);
+        let venue = self.normalization.apply_str(venue);
+        self.intern_normalized(&code, &venue)
+    }
+
+    /// Normalizes and validates `code` and `venue` and interns them.
+    pub fn try_intern_bytes(&mut self, code: &[u8], venue: &[u8]) -> Result<Interned<DynamicEntry>, IdError> {
+        let code = std::str::from_utf8(code).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Code })?;
+        let venue = std::str::from_utf8(venue).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Venue })?;
+        let code = self.normalization.apply_str(code);
+        self.code_validation.check(IdPart::Code, &code)?;
+        let venue = self.normalization.apply_str(venue);
+        self.venue_validation.check(IdPart::Venue, &venue)?;
+        Ok(self.intern_normalized(&code, &venue))
+    }
+
+    fn intern_normalized(&mut self, code: &str, venue: &str) -> Interned<DynamicEntry> {
+        let hash = hash_parts(code.as_bytes(), venue.as_bytes());
+        if let Some(interned) = self.find(hash, code, venue) {
+            return interned;
+        }
+        let index = u32::try_from(self.index.len()).expect("more than u32::MAX ids interned");
+        let entry = DynamicEntry {
+            code: self.arena.alloc(code),
+            venue: self.arena.alloc(venue),
+            index,
+            hash,
+            fingerprint: fingerprint64(code, venue),
+            fingerprint128: fingerprint128(code, venue),
+        };
+        let interned = self.map.insert_new(hash, entry);
+        self.index.push(interned);
+        interned
+    }
+
+    #[inline]
+    fn find(&self, hash: u64, code: &str, venue: &str) -> Option<Interned<DynamicEntry>> {
+        self.map.find(hash, |entry| entry.code == code && entry.venue == venue)
+    }
+
+    /// Normalizes `code` and `venue` and returns their interned id without interning it.
+    pub fn get_str(&self, code: &str, venue: &str) -> Option<Interned<DynamicEntry>> {
+        let code = self.normalization.apply_str(code);
+        let venue = self.normalization.apply_str(venue);
+        self.find(hash_parts(code.as_bytes(), venue.as_bytes()), &code, &venue)
+    }
+
+    /// Returns the id interned at `index`, see [`DynamicEntry::index`].
+    #[inline]
+    pub fn get_by_index(&self, index: u32) -> Option<Interned<DynamicEntry>> {
+        self.index.get(index)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// The bytes of code and venue text held by the cache.
+    #[inline]
+    pub fn text_bytes(&self) -> usize {
+        self.arena.bytes
+    }
+
+    /// All interned ids whose code is `code`, in intern order.
+    #[inline]
+    pub fn ids_for_code(&self, code: &str) -> &[Interned<DynamicEntry>] {
+        self.index.ids_for_code(&self.normalization.apply_str(code))
+    }
+
+    /// All interned ids whose venue is `venue`, in intern order.
+    #[inline]
+    pub fn ids_for_venue(&self, venue: &str) -> &[Interned<DynamicEntry>] {
+        self.index.ids_for_venue(&self.normalization.apply_str(venue))
+    }
+
+    /// All interned ids whose code starts with `prefix`, sorted by code,
+    /// see `IdCacheNxM::search_prefix`.
+    pub fn search_prefix(&self, prefix: &str, venue: Option<&str>) -> Vec<Interned<DynamicEntry>> {
+        let venue = venue.map(|venue| self.normalization.apply_str(venue));
+        self.index.search_prefix(&self.normalization.apply_str(prefix), venue.as_deref())
+    }
+
+    /// All interned ids whose code matches the glob `code_pattern`, sorted by
+    /// code, see `IdCacheNxM::search_glob`.
+    pub fn search_glob(&self, code_pattern: &str, venue_pattern: Option<&str>) -> Vec<Interned<DynamicEntry>> {
+        let venue_pattern = venue_pattern.map(|pattern| self.normalization.apply_str(pattern));
+        self.index.search_glob(&self.normalization.apply_str(code_pattern), venue_pattern.as_deref())
+    }
+
+    /// The `limit` interned ids closest to `code` and `venue`, see `IdCacheNxM::similar`.
+    pub fn similar(&self, code: &str, venue: &str, limit: usize) -> Vec<(Interned<DynamicEntry>, usize)> {
+        let code = self.normalization.apply_str(code);
+        let venue = self.normalization.apply_str(venue);
+        self.index.similar(&code, &venue, limit)
+    }
+}
+
+static DYNAMIC_CACHE: Lazy<Mutex<DynamicIdCache>> = Lazy::new(|| Mutex::new(DynamicIdCache::new()));
+static DYNAMIC_FORMAT: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static DYNAMIC_SERDE_REPR: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
+static DEFAULT_DYNAMIC_ID: Lazy<DynamicId> = Lazy::new(|| DynamicId::from_str("", ""));
+
+/// An interned id of unbounded length, see the [module docs](self).
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct DynamicId {
+    pub(crate) id_ptr: Interned<DynamicEntry>,
+}
+
+/// Writes the hash precomputed at intern time, like `StaticIdNxM`.
+impl Hash for DynamicId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.id_ptr.hash);
+    }
+}
+
+/// Orders ids lexicographically by code, then by venue, like `StaticIdNxM`.
+impl Ord for DynamicId {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        if self == other {
+            return std::cmp::Ordering::Equal;
+        }
+        (self.code_str(), self.venue_str()).cmp(&(other.code_str(), other.venue_str()))
+    }
+}
+
+impl PartialOrd for DynamicId {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl DynamicId {
+    /// The intern cache of `DynamicId`.
+    #[inline]
+    pub(crate) fn id_cache() -> &'static Mutex<DynamicIdCache> {
+        &DYNAMIC_CACHE
+    }
+
+    /// Locks the intern cache. The cache only panics before it changes
+    /// anything, so a poisoned lock is recovered instead of failing every
+    /// later call.
+    #[inline]
+    fn lock_id_cache() -> MutexGuard<'static, DynamicIdCache> {
+        Self::id_cache().lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    #[must_use]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(code: &str, venue: &str) -> Self {
+        let id_ptr = Self::lock_id_cache().intern_str(code, venue);
+        DynamicId { id_ptr }
+    }
+
+    /// Interns `code` and `venue`, replacing invalid UTF-8 with `U+FFFD`.
+    #[inline]
+    #[must_use]
+    pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
+        Self::from_str(&String::from_utf8_lossy(code), &String::from_utf8_lossy(venue))
+    }
+
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        Self::try_from_bytes(code.as_bytes(), venue.as_bytes())
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let id_ptr = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(DynamicId { id_ptr })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn from_combined_str(combined: &str) -> Self {
+        Self::from_combined_str_with(combined, Self::format())
+    }
+
+    /// Parses `combined` in the given `format` and interns it.
+    ///
+    /// Unlike the fallible parser, keeps invalid escape sequences and later
+    /// separators as written, see [`IdFormat::split_lenient`].
+    ///
+    /// # Panics
+    /// Panics if `combined` does not contain the separator of `format`.
+    pub fn from_combined_str_with(combined: &str, format: IdFormat) -> Self {
+        let (code, venue) = format.split_lenient(combined).unwrap();
+        Self::from_str(&code, &venue)
+    }
+
+    /// Parses `combined` in the given `format`, validates it and interns it.
+    pub fn try_from_combined_str_with(combined: &str, format: IdFormat) -> Result<Self, IdError> {
+        let (code, venue) = format.split(combined)?;
+        Self::try_from_str(&code, &venue)
+    }
+
+    /// Returns the id for `code` and `venue` if it has already been interned.
+    pub fn lookup(code: &str, venue: &str) -> Option<Self> {
+        Self::lock_id_cache().get_str(code, venue).map(|id_ptr| DynamicId { id_ptr })
+    }
+
+    /// Returns the id interned at `index`, see [`DynamicEntry::index`].
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::lock_id_cache().get_by_index(index).map(|id_ptr| DynamicId { id_ptr })
+    }
+
+    #[inline]
+    pub fn cache_len() -> usize {
+        Self::lock_id_cache().len()
+    }
+
+    /// The bytes of code and venue text held by the intern cache.
+    #[inline]
+    pub fn cache_text_bytes() -> usize {
+        Self::lock_id_cache().text_bytes()
+    }
+
+    /// The `limit` interned ids most similar to `code` and `venue`, best
+    /// first, together with their edit distance, see `StaticIdNxM::similar`.
+    pub fn similar(code: &str, venue: &str, limit: usize) -> Vec<(Self, usize)> {
+        let cache = Self::lock_id_cache();
+        cache
+            .similar(code, venue, limit)
+            .into_iter()
+            .map(|(id_ptr, distance)| (DynamicId { id_ptr }, distance))
+            .collect()
+    }
+
+    /// Looks up an id without interning it, and suggests up to `limit`
+    /// "did you mean" candidates if it is unknown.
+    pub fn lookup_or_suggest(code: &str, venue: &str, limit: usize) -> Result<Self, Vec<Self>> {
+        match Self::lookup(code, venue) {
+            Some(id) => Ok(id),
+            None => Err(Self::similar(code, venue, limit).into_iter().map(|(id, _)| id).collect()),
+        }
+    }
+
+    /// Every interned id traded on `venue`, in intern order.
+    pub fn ids_for_venue(venue: &str) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.ids_for_venue(venue).iter().map(|&id_ptr| DynamicId { id_ptr }).collect()
+    }
+
+    /// Every interned id with the given `code`, one per venue, in intern order.
+    pub fn ids_for_code(code: &str) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.ids_for_code(code).iter().map(|&id_ptr| DynamicId { id_ptr }).collect()
+    }
+
+    /// Every interned id whose code starts with `prefix`, sorted by code,
+    /// optionally restricted to one venue.
+    pub fn search_prefix(prefix: &str, venue: Option<&str>) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.search_prefix(prefix, venue).into_iter().map(|id_ptr| DynamicId { id_ptr }).collect()
+    }
+
+    /// Every interned id matching a glob query, sorted by code, see
+    /// `StaticIdNxM::search_glob`.
+    pub fn search_glob(query: &str) -> Vec<Self> {
+        let (code_pattern, venue_pattern) = Self::format().split_query(query);
+        let cache = Self::lock_id_cache();
+        cache
+            .search_glob(&code_pattern, venue_pattern.as_deref())
+            .into_iter()
+            .map(|id_ptr| DynamicId { id_ptr })
+            .collect()
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.id_ptr.code.len() + self.id_ptr.venue.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.id_ptr.code.is_empty() && self.id_ptr.venue.is_empty()
+    }
+
+    /// The interned entry of the id.
+    #[inline]
+    pub fn entry(&self) -> &'static DynamicEntry {
+        Interned::as_static(self.id_ptr)
+    }
+
+    /// The dense intern index of the id, see [`DynamicEntry::index`].
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.id_ptr.index
+    }
+
+    /// The 64-bit content fingerprint of the id, see [`crate::fingerprint`].
+    /// Equal to that of a `StaticIdNxM` holding the same text.
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.id_ptr.fingerprint
+    }
+
+    /// The 128-bit content fingerprint of the id, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint128(&self) -> u128 {
+        self.id_ptr.fingerprint128
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn code_str(&self) -> &'static str {
+        self.id_ptr.code
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn venue_str(&self) -> &'static str {
+        self.id_ptr.venue
+    }
+
+    /// Displays the id in `format` instead of the format of its type.
+    #[inline]
+    pub fn display_with(&self, format: IdFormat) -> DynamicDisplayWith<'_> {
+        DynamicDisplayWith { id: self, format }
+    }
+
+    /// The normalization policy applied to codes and venues.
+    pub fn normalization() -> Normalization {
+        Self::lock_id_cache().normalization()
+    }
+
+    /// Sets the normalization policy, see `StaticIdNxM::set_normalization`.
+    pub fn set_normalization(normalization: Normalization) {
+        Self::lock_id_cache().set_normalization(normalization);
+    }
+
+    /// Sets the validation policies enforced by the fallible constructors
+    /// and by `Deserialize`.
+    pub fn set_validation(code: Validation, venue: Validation) {
+        Self::lock_id_cache().set_validation(code, venue);
+    }
+
+    pub fn code_validation() -> Validation {
+        Self::lock_id_cache().code_validation()
+    }
+
+    pub fn venue_validation() -> Validation {
+        Self::lock_id_cache().venue_validation()
+    }
+
+    /// The text format used by `Display`, `from_combined_str` and serde.
+    #[inline]
+    pub fn format() -> IdFormat {
+        DYNAMIC_FORMAT.load()
+    }
+
+    #[inline]
+    pub fn set_format(format: IdFormat) {
+        DYNAMIC_FORMAT.store(format);
+    }
+
+    /// The representation used by `Serialize` and `Deserialize`: its own if
+    /// set, else the process-wide default.
+    #[inline]
+    pub fn serde_repr() -> SerdeRepr {
+        DYNAMIC_SERDE_REPR.load().unwrap_or_else(serde_repr::default_repr)
+    }
+
+    #[inline]
+    pub fn set_serde_repr(repr: Option<SerdeRepr>) {
+        DYNAMIC_SERDE_REPR.store(repr);
+    }
+}
+
+impl Default for DynamicId {
+    fn default() -> Self {
+        *DEFAULT_DYNAMIC_ID
+    }
+}
+
+/// Displays a `DynamicId` in a given format. Returned by `DynamicId::display_with`.
+pub struct DynamicDisplayWith<'a> {
+    id: &'a DynamicId,
+    format: IdFormat,
+}
+
+impl fmt::Display for DynamicDisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format.write(f, self.id.code_str(), self.id.venue_str())
+    }
+}
+
+impl fmt::Display for DynamicId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(Self::format()))
+    }
+}
+
+impl fmt::Debug for DynamicId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_with(Self::format()))
+    }
+}
+
+impl Serialize for DynamicId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match Self::serde_repr() {
+            SerdeRepr::Combined => serializer.collect_str(self),
+            SerdeRepr::Tuple => (self.code_str(), self.venue_str()).serialize(serializer),
+            SerdeRepr::Struct => serde_repr::structure::PartsRef { code: self.code_str(), venue: self.venue_str() }
+                .serialize(serializer),
+            SerdeRepr::Index => serializer.serialize_u32(self.index()),
+        }
+    }
+}
+
+/// Interns a combined string from the transient string.
+struct CombinedVisitor;
+
+impl Visitor<'_> for CombinedVisitor {
+    type Value = DynamicId;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a combined id string such as \"AAPL{}NASDAQ\"", DynamicId::format().separator())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        DynamicId::try_from_combined_str(v).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(v)
+    }
+}
+
+impl<'de> Deserialize<'de> for DynamicId {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Self::serde_repr() {
+            SerdeRepr::Combined => deserializer.deserialize_str(CombinedVisitor),
+            SerdeRepr::Tuple => {
+                let (CowStr(code), CowStr(venue)) = <(CowStr, CowStr)>::deserialize(deserializer)?;
+                Self::try_from_str(&code, &venue).map_err(de::Error::custom)
+            }
+            SerdeRepr::Struct => {
+                let serde_repr::structure::Parts { code: CowStr(code), venue: CowStr(venue) } =
+                    serde_repr::structure::Parts::deserialize(deserializer)?;
+                Self::try_from_str(&code, &venue).map_err(de::Error::custom)
+            }
+            SerdeRepr::Index => {
+                let index = u32::deserialize(deserializer)?;
+                Self::from_index(index)
+                    .ok_or_else(|| de::Error::custom(format!("no id is interned at index {}", index)))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arena() {
+        let mut arena = StrArena::default();
+        let a = arena.alloc("AAPL");
+        let b = arena.alloc("NASDAQ");
+        assert_eq!((a, b), ("AAPL", "NASDAQ"));
+        assert_eq!(a.as_ptr().wrapping_add(4), b.as_ptr());
+
+        let long = "X".repeat(CHUNK);
+        assert_eq!(arena.alloc(&long), long);
+        assert_eq!(arena.alloc(""), "");
+        assert_eq!(arena.bytes, 10 + CHUNK);
+    }
+}
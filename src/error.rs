@@ -0,0 +1,92 @@
+use std::fmt;
+
+/// Which half of an id a problem was found in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IdPart {
+    Code,
+    Venue,
+}
+
+impl fmt::Display for IdPart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdPart::Code => write!(f, "code"),
+            IdPart::Venue => write!(f, "venue"),
+        }
+    }
+}
+
+/// Errors returned by the fallible constructors and by `Deserialize`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IdError {
+    /// The bytes of the code or venue are not valid UTF-8.
+    InvalidUtf8 { part: IdPart },
+    /// The code or venue is shorter than the minimum length of the validation policy.
+    TooShort { part: IdPart, value: String, min_len: usize },
+    /// The code or venue contains a character outside the allowed character classes.
+    DisallowedChar { part: IdPart, value: String, ch: char, position: usize },
+    /// The custom predicate of the validation policy rejected the code or venue.
+    Rejected { part: IdPart, value: String, reason: String },
+    /// A combined string does not contain the separator between code and venue.
+    MissingSeparator { combined: String, separator: char },
+    /// A combined string contains an escape character that is not followed by the
+    /// separator or the escape character, or a second unescaped separator.
+    InvalidEscape { combined: String, position: usize },
+    /// The code or venue is longer than the bound of the type and its truncation policy rejects it.
+    TooLong { part: IdPart, value: String, max_len: usize },
+    /// A raw core does not have the size of the core type.
+    SizeMismatch { expected: usize, found: usize },
+    /// Raw consecutive cores are not a whole number of cores of size `core_size`.
+    SliceSizeMismatch { core_size: usize, found: usize },
+    /// The bytes after the end of a raw code or venue are not all the `0xFF` terminator.
+    InvalidPadding { part: IdPart },
+}
+
+impl fmt::Display for IdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IdError::InvalidUtf8 { part } => write!(f, "{} is not valid UTF-8", part),
+            IdError::TooShort { part, value, min_len } => write!(
+                f,
+                "{} {:?} is too short: expected at least {} bytes, found {}",
+                part, value, min_len, value.len()
+            ),
+            IdError::DisallowedChar { part, value, ch, position } => write!(
+                f,
+                "{} {:?} contains the disallowed character {:?} at byte {}",
+                part, value, ch, position
+            ),
+            IdError::Rejected { part, value, reason } => {
+                write!(f, "{} {:?} is rejected: {}", part, value, reason)
+            }
+            IdError::MissingSeparator { combined, separator } => write!(
+                f,
+                "{:?} is not a combined id: separator {:?} not found",
+                combined, separator
+            ),
+            IdError::InvalidEscape { combined, position } => write!(
+                f,
+                "{:?} is not a combined id: invalid escape sequence at byte {}",
+                combined, position
+            ),
+            IdError::TooLong { part, value, max_len } => write!(
+                f,
+                "{} {:?} is too long: expected at most {} bytes, found {}",
+                part, value, max_len, value.len()
+            ),
+            IdError::SizeMismatch { expected, found } => {
+                write!(f, "raw core has {} bytes, expected {}", found, expected)
+            }
+            IdError::SliceSizeMismatch { core_size, found } => write!(
+                f,
+                "raw cores have {} bytes, expected a multiple of {}",
+                found, core_size
+            ),
+            IdError::InvalidPadding { part } => {
+                write!(f, "{} is not padded with the 0xFF terminator", part)
+            }
+        }
+    }
+}
+
+impl std::error::Error for IdError {}
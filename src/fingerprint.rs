@@ -0,0 +1,92 @@
+//! Content fingerprints of ids, stable across processes, runs and machines.
+//!
+//! Equality and `Hash` of `StaticIdNxM` are only meaningful within one process.
+//! A fingerprint is computed from the text of the id alone, with a fixed
+//! algorithm, so it can serve as a key shared with other processes or stored
+//! on disk:
+//!
+//! - the input is the UTF-8 bytes of the code, the byte `0xFF`, then the UTF-8
+//!   bytes of the venue (`0xFF` never occurs in UTF-8, so the split is unambiguous);
+//! - [`fingerprint64`] is 64-bit FNV-1a of the input
+//!   (offset basis `0xcbf29ce484222325`, prime `0x100000001b3`);
+//! - [`fingerprint128`] is 128-bit FNV-1a of the input
+//!   (offset basis `0x6c62272e07bb014262b821756295c58d`, prime `0x1000000000000000000013b`).
+//!
+//! The fingerprint does not depend on the `N` and `M` bounds of the type.
+//! Ids of types without a venue fingerprint with an empty venue.
+//!
+//! ```rust
+//! use static_id::fingerprint::fingerprint64;
+//! use static_id::StaticId;
+//!
+//! let id = StaticId::from_str("AAPL", "NASDAQ");
+//! assert_eq!(id.fingerprint(), 0x624e_789b_54df_8798);
+//! assert_eq!(id.fingerprint(), fingerprint64("AAPL", "NASDAQ"));
+//! ```
+
+const SEPARATOR: u8 = 0xFF;
+
+const OFFSET_64: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME_64: u64 = 0x0100_0000_01b3;
+const OFFSET_128: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d;
+const PRIME_128: u128 = 0x0000_0000_0100_0000_0000_0000_0000_013b;
+
+/// The 64-bit fingerprint of the id with `code` and `venue`.
+pub fn fingerprint64(code: &str, venue: &str) -> u64 {
+    let hash = fnv1a_64(OFFSET_64, code.as_bytes());
+    let hash = fnv1a_64(hash, &[SEPARATOR]);
+    fnv1a_64(hash, venue.as_bytes())
+}
+
+/// The 128-bit fingerprint of the id with `code` and `venue`.
+pub fn fingerprint128(code: &str, venue: &str) -> u128 {
+    let hash = fnv1a_128(OFFSET_128, code.as_bytes());
+    let hash = fnv1a_128(hash, &[SEPARATOR]);
+    fnv1a_128(hash, venue.as_bytes())
+}
+
+/// 64-bit FNV-1a of `bytes`.
+#[inline]
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    fnv1a_64(OFFSET_64, bytes)
+}
+
+#[inline]
+fn fnv1a_64(mut hash: u64, bytes: &[u8]) -> u64 {
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(PRIME_64);
+    }
+    hash
+}
+
+#[inline]
+fn fnv1a_128(mut hash: u128, bytes: &[u8]) -> u128 {
+    for &byte in bytes {
+        hash ^= u128::from(byte);
+        hash = hash.wrapping_mul(PRIME_128);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+        assert_eq!(fnv1a_128(OFFSET_128, b"a"), 0xd228_cb69_6f1a_8caf_7891_2b70_4e4a_8964);
+    }
+
+    #[test]
+    fn test_fingerprints() {
+        assert_eq!(fingerprint64("", ""), 0xaf64_724c_8602_eb6e);
+        assert_eq!(fingerprint64("005930", ""), 0x73cb_471b_1e3b_b42d);
+        assert_eq!(fingerprint128("", ""), 0xd228_cb68_f51a_8caf_7891_2b70_4e49_f346);
+        assert_eq!(fingerprint128("AAPL", "NASDAQ"), 0xf9e3_8aca_3634_dd79_62e2_a342_4ec4_ad98);
+        assert_ne!(fingerprint64("AB", "C"), fingerprint64("A", "BC"));
+    }
+}
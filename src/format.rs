@@ -0,0 +1,324 @@
+use crate::error::IdError;
+use crate::static_id::StaticIdNxM;
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Text format of the combined form of an id, e.g. `"AAPL@NASDAQ"`.
+///
+/// Each `StaticIdNxM` type has a format, used by `Display`, `from_combined_str`
+/// and serde, and every format-aware method also has a `_with` variant taking
+/// the format per call.
+///
+/// A separator or escape character inside the code or venue is written with
+/// the escape character in front of it, so every id round-trips exactly:
+/// with the default format, code `"A@B"` on venue `"X"` is written `"A\@B@X"`.
+/// When parsing, the escape character may only be followed by the separator
+/// or by itself; the infallible constructors, which use [`IdFormat::split_lenient`],
+/// keep other escape characters and later separators as written.
+///
+/// ```rust
+/// use static_id::{IdFormat, StaticId};
+///
+/// let reuters = IdFormat::new('.');
+/// let id = StaticId::from_combined_str_with("AAPL.O", reuters);
+/// assert_eq!(id.code_str(), "AAPL");
+/// assert_eq!(id.display_with(reuters).to_string(), "AAPL.O");
+/// assert_eq!(id.to_string(), "AAPL@O");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct IdFormat {
+    separator: char,
+    escape: char,
+}
+
+impl IdFormat {
+    /// `"code@venue"`, escaped with `'\\'`
+    pub const DEFAULT: Self = Self::new('@');
+
+    /// A format with the given separator and `'\\'` as escape character.
+    ///
+    /// # Panics
+    /// Panics if `separator` is `'\\'`.
+    #[inline]
+    pub const fn new(separator: char) -> Self {
+        Self::with_escape(separator, '\\')
+    }
+
+    /// A format with the given separator and escape character.
+    ///
+    /// # Panics
+    /// Panics if `separator` and `escape` are the same character.
+    #[inline]
+    pub const fn with_escape(separator: char, escape: char) -> Self {
+        assert!(separator != escape, "separator and escape character must differ");
+        Self { separator, escape }
+    }
+
+    /// Separator between code and venue.
+    #[inline]
+    pub const fn separator(&self) -> char {
+        self.separator
+    }
+
+    /// Escape character for separators and escape characters inside code and venue.
+    #[inline]
+    pub const fn escape(&self) -> char {
+        self.escape
+    }
+
+    /// Splits a combined string into code and venue at the first unescaped
+    /// separator, and unescapes both halves.
+    pub fn split<'a>(&self, combined: &'a str) -> Result<(Cow<'a, str>, Cow<'a, str>), IdError> {
+        self.split_parts(combined, true)
+    }
+
+    /// Like [`Self::split`], but an escape character that is not followed by
+    /// the separator or by itself, and any unescaped separator in the venue,
+    /// are kept as written instead of being rejected.
+    ///
+    /// ```rust
+    /// use static_id::IdFormat;
+    ///
+    /// let (code, venue) = IdFormat::DEFAULT.split_lenient(r"A\B@X@Y").unwrap();
+    /// assert_eq!((&*code, &*venue), (r"A\B", "X@Y"));
+    /// assert!(IdFormat::DEFAULT.split(r"A\B@X").is_err());
+    /// ```
+    pub fn split_lenient<'a>(&self, combined: &'a str) -> Result<(Cow<'a, str>, Cow<'a, str>), IdError> {
+        self.split_parts(combined, false)
+    }
+
+    /// Splits a search query into a code pattern and, if the query has an
+    /// unescaped separator, a venue pattern, unescaping both leniently.
+    pub(crate) fn split_query<'a>(&self, query: &'a str) -> (Cow<'a, str>, Option<Cow<'a, str>>) {
+        match self.split_parts(query, false) {
+            Ok((code, venue)) => (code, Some(venue)),
+            // lenient unescaping only fails on a missing separator, and never here
+            Err(_) => (self.unescape(query, 0, false).unwrap_or(Cow::Borrowed(query)), None),
+        }
+    }
+
+    fn split_parts<'a>(&self, combined: &'a str, strict: bool) -> Result<(Cow<'a, str>, Cow<'a, str>), IdError> {
+        if !combined.contains(self.escape) {
+            let position = combined.find(self.separator).ok_or_else(|| self.missing_separator(combined))?;
+            let venue = self.unescape(combined, position + self.separator.len_utf8(), strict)?;
+            return Ok((Cow::Borrowed(&combined[..position]), venue));
+        }
+
+        let mut code = String::with_capacity(combined.len());
+        let mut chars = combined.char_indices();
+        while let Some((position, ch)) = chars.next() {
+            if ch == self.separator {
+                let venue = self.unescape(combined, position + ch.len_utf8(), strict)?;
+                return Ok((Cow::Owned(code), venue));
+            }
+            if ch == self.escape {
+                self.push_escaped(&mut code, combined, position, chars.next(), strict)?;
+            } else {
+                code.push(ch);
+            }
+        }
+        Err(self.missing_separator(combined))
+    }
+
+    /// Unescapes `combined[start..]`, which must not contain unescaped separators if `strict`.
+    fn unescape<'a>(&self, combined: &'a str, start: usize, strict: bool) -> Result<Cow<'a, str>, IdError> {
+        let rest = &combined[start..];
+        if !rest.contains(self.escape) && (!strict || !rest.contains(self.separator)) {
+            return Ok(Cow::Borrowed(rest));
+        }
+
+        let mut unescaped = String::with_capacity(rest.len());
+        let mut chars = rest.char_indices().map(|(position, ch)| (start + position, ch));
+        while let Some((position, ch)) = chars.next() {
+            if ch == self.escape {
+                self.push_escaped(&mut unescaped, combined, position, chars.next(), strict)?;
+            } else if ch == self.separator && strict {
+                return Err(IdError::InvalidEscape {
+                    combined: combined.to_string(),
+                    position,
+                });
+            } else {
+                unescaped.push(ch);
+            }
+        }
+        Ok(Cow::Owned(unescaped))
+    }
+
+    /// Pushes the text of the escape character at `position`, followed by
+    /// `next`: the escaped character, or, unless `strict`, both as written.
+    #[inline]
+    fn push_escaped(
+        &self,
+        out: &mut String,
+        combined: &str,
+        position: usize,
+        next: Option<(usize, char)>,
+        strict: bool,
+    ) -> Result<(), IdError> {
+        match next {
+            Some((_, ch)) if ch == self.separator || ch == self.escape => out.push(ch),
+            _ if strict => {
+                return Err(IdError::InvalidEscape {
+                    combined: combined.to_string(),
+                    position,
+                })
+            }
+            Some((_, ch)) => {
+                out.push(self.escape);
+                out.push(ch);
+            }
+            None => out.push(self.escape),
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn missing_separator(&self, combined: &str) -> IdError {
+        IdError::MissingSeparator {
+            combined: combined.to_string(),
+            separator: self.separator,
+        }
+    }
+
+    /// Writes the combined form of `code` and `venue`, escaping both.
+    #[inline]
+    pub fn write<W: fmt::Write>(&self, out: &mut W, code: &str, venue: &str) -> fmt::Result {
+        self.write_escaped(out, code)?;
+        out.write_char(self.separator)?;
+        self.write_escaped(out, venue)
+    }
+
+    #[inline]
+    fn write_escaped<W: fmt::Write>(&self, out: &mut W, part: &str) -> fmt::Result {
+        if !part.contains([self.separator, self.escape]) {
+            return out.write_str(part);
+        }
+        for ch in part.chars() {
+            if ch == self.separator || ch == self.escape {
+                out.write_char(self.escape)?;
+            }
+            out.write_char(ch)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    const fn to_bits(self) -> u64 {
+        self.separator as u64 | (self.escape as u64) << 32
+    }
+
+    #[inline]
+    fn from_bits(bits: u64) -> Self {
+        Self::with_escape(
+            char::from_u32(bits as u32).unwrap(),
+            char::from_u32((bits >> 32) as u32).unwrap(),
+        )
+    }
+}
+
+impl Default for IdFormat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// An [`IdFormat`] that can be read and replaced without locking.
+///
+/// Every id type keeps its format in one of these, so that `Display` does
+/// not need the intern cache lock.
+pub struct AtomicIdFormat(AtomicU64);
+
+impl AtomicIdFormat {
+    pub const fn new(format: IdFormat) -> Self {
+        Self(AtomicU64::new(format.to_bits()))
+    }
+
+    #[inline]
+    pub fn load(&self) -> IdFormat {
+        IdFormat::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    pub fn store(&self, format: IdFormat) {
+        self.0.store(format.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Displays an id in a given format. Returned by `StaticIdNxM::display_with`.
+pub struct DisplayWith<'a, const N: usize, const M: usize> {
+    pub(crate) id: &'a StaticIdNxM<N, M>,
+    pub(crate) format: IdFormat,
+}
+
+impl<const N: usize, const M: usize> fmt::Display for DisplayWith<'_, N, M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.format.write(f, self.id.code_str(), self.id.venue_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(format: IdFormat, code: &str, venue: &str) {
+        let mut combined = String::new();
+        format.write(&mut combined, code, venue).unwrap();
+        let (parsed_code, parsed_venue) = format.split(&combined).unwrap();
+        assert_eq!((&*parsed_code, &*parsed_venue), (code, venue), "via {:?}", combined);
+    }
+
+    #[test]
+    fn test_split() {
+        let format = IdFormat::new(':');
+        assert_eq!(format.split("AAPL:US").unwrap(), ("AAPL".into(), "US".into()));
+        assert_eq!(format.split("AAPL:").unwrap(), ("AAPL".into(), "".into()));
+        assert!(format.split("AAPL@US").is_err());
+    }
+
+    #[test]
+    fn test_escaping() {
+        let format = IdFormat::DEFAULT;
+        let mut combined = String::new();
+        format.write(&mut combined, "A@B", "X\\Y").unwrap();
+        assert_eq!(combined, "A\\@B@X\\\\Y");
+        assert_eq!(format.split(&combined).unwrap(), ("A@B".into(), "X\\Y".into()));
+    }
+
+    #[test]
+    fn test_adversarial_round_trips() {
+        let formats = [IdFormat::DEFAULT, IdFormat::new('-'), IdFormat::with_escape('.', '^')];
+        let parts = ["", "@", "\\", "@@", "\\@", "@\\", "\\\\", "A@", "@A", "A\\", "-", "a-b", ".^", "^.", "삼성@전자", "é\\@"];
+        for format in formats {
+            for code in parts {
+                for venue in parts {
+                    round_trip(format, code, venue);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_escapes() {
+        let format = IdFormat::DEFAULT;
+        assert_eq!(
+            format.split("A\\B@X"),
+            Err(IdError::InvalidEscape { combined: "A\\B@X".to_string(), position: 1 })
+        );
+        assert!(matches!(format.split("A@X\\"), Err(IdError::InvalidEscape { position: 3, .. })));
+        assert!(matches!(format.split("A@X@Y\\@"), Err(IdError::InvalidEscape { position: 3, .. })));
+        assert!(matches!(format.split("A@X@Y"), Err(IdError::InvalidEscape { position: 3, .. })));
+        assert!(matches!(format.split("A\\@X"), Err(IdError::MissingSeparator { .. })));
+        assert!(matches!(format.split("\\"), Err(IdError::InvalidEscape { position: 0, .. })));
+    }
+
+    #[test]
+    fn test_atomic_format() {
+        let cell = AtomicIdFormat::new(IdFormat::DEFAULT);
+        assert_eq!(cell.load(), IdFormat::DEFAULT);
+        cell.store(IdFormat::new('-'));
+        assert_eq!(cell.load().separator, '-');
+        cell.store(IdFormat::with_escape('→', '☃'));
+        assert_eq!(cell.load(), IdFormat::with_escape('→', '☃'));
+    }
+}
@@ -0,0 +1,268 @@
+//! Ids that hold short codes inline instead of interning them.
+//!
+//! A [`HybridIdNxM`] is a tagged `u64`. When the code and venue together fit
+//! in 7 bytes, e.g. `"AAPL"` on `"KRX"` or `"IBM"` on `"NYSE"`, the id holds
+//! them directly: creating it takes no lock and leaks no allocation. Longer ids
+//! hold a pointer to the entry interned in the cache of `StaticIdNxM<N, M>`.
+//! An id that fits inline is always inline, so equality and hashing remain a
+//! single 8-byte comparison in both cases.
+//!
+//! Hybrid ids hold their text as given: the normalization and validation
+//! policies of `StaticIdNxM<N, M>` do not apply to them, and converting an
+//! inline id with [`HybridIdNxM::to_static_id`] interns its text verbatim.
+//! Those policies live in the cache, behind its lock, which an inline id is
+//! created without; applying them to long ids only would make the same text
+//! mean different ids depending on its length. This holds for `Deserialize`
+//! as well.
+//! Text longer than `N` or `M` bytes is never inline, and is fitted by the
+//! [`Truncation`](crate::Truncation) policy of `StaticIdNxM<N, M>`.
+//!
+//! ```rust
+//! use static_id::hybrid::HybridId;
+//! use static_id::StaticId;
+//!
+//! let short = HybridId::from_str("AAPL", "KRX");
+//! assert!(short.is_inline());
+//! assert_eq!(short.code_str(), "AAPL");
+//!
+//! let long = HybridId::from_str("KR7005930003", "KRX");
+//! assert!(!long.is_inline());
+//! assert_eq!(long.to_static_id(), StaticId::from_str("KR7005930003", "KRX"));
+//! assert_eq!(long.to_string(), "KR7005930003@KRX");
+//! ```
+use crate::error::IdError;
+use crate::id_cache::HasIdCache;
+use crate::interned::Interned;
+use crate::static_id::{IdEntryNxM, StaticIdNxM};
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+
+/// Largest number of code and venue bytes held inline.
+pub const INLINE_CAPACITY: usize = 7;
+
+// The tag byte is the least significant byte of the `u64`: its low bit is set
+// for inline ids and clear for pointers, which are at least 2-aligned. It also
+// holds the code length in bits 1..4 and the venue length in bits 4..7. The
+// other 7 bytes hold the code then the venue, padded with zeros.
+#[cfg(target_endian = "little")]
+const TAG: usize = 0;
+#[cfg(target_endian = "little")]
+const PAYLOAD: usize = 1;
+#[cfg(target_endian = "big")]
+const TAG: usize = 7;
+#[cfg(target_endian = "big")]
+const PAYLOAD: usize = 0;
+
+/// An id holding short codes inline and longer ones as an interned pointer,
+/// see the [module docs](self).
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HybridIdNxM<const N: usize, const M: usize> {
+    bits: u64,
+}
+
+impl<const N: usize, const M: usize> HybridIdNxM<N, M> {
+    /// The inline id for `code` and `venue` as stored by `StaticIdNxM<N, M>`,
+    /// if they fit.
+    fn inline(code: &str, venue: &str) -> Option<Self> {
+        if code.len() > N || venue.len() > M || code.len() + venue.len() > INLINE_CAPACITY {
+            return None;
+        }
+        let mut bytes = [0u8; 8];
+        bytes[TAG] = 1 | (code.len() as u8) << 1 | (venue.len() as u8) << 4;
+        bytes[PAYLOAD..PAYLOAD + code.len()].copy_from_slice(code.as_bytes());
+        bytes[PAYLOAD + code.len()..PAYLOAD + code.len() + venue.len()].copy_from_slice(venue.as_bytes());
+        Some(Self { bits: u64::from_ne_bytes(bytes) })
+    }
+
+    #[inline]
+    fn from_entry(entry: &'static IdEntryNxM<N, M>) -> Self {
+        const { assert!(std::mem::align_of::<IdEntryNxM<N, M>>() >= 2) };
+        let ptr: *const IdEntryNxM<N, M> = entry;
+        Self { bits: ptr.expose_provenance() as u64 }
+    }
+
+    #[inline]
+    fn entry(&self) -> Option<&'static IdEntryNxM<N, M>> {
+        if self.is_inline() {
+            return None;
+        }
+        let ptr = std::ptr::with_exposed_provenance::<IdEntryNxM<N, M>>(self.bits as usize);
+        // Safety: non-inline ids are only made by `from_entry`, from a leaked entry
+        Some(unsafe { &*ptr })
+    }
+
+    #[inline]
+    fn bytes(&self) -> &[u8; 8] {
+        // Safety: `[u8; 8]` has the size of `u64` and a smaller alignment
+        unsafe { &*(&self.bits as *const u64 as *const [u8; 8]) }
+    }
+
+    /// Whether the id holds its text inline rather than pointing into the cache.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.bits & 1 == 1
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn code_str(&self) -> &str {
+        match self.entry() {
+            Some(entry) => entry.code.as_str(),
+            None => {
+                let len = (self.bytes()[TAG] >> 1 & 0b111) as usize;
+                // Safety: inline ids are only made by `inline`, from a `&str`
+                unsafe { std::str::from_utf8_unchecked(&self.bytes()[PAYLOAD..PAYLOAD + len]) }
+            }
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn venue_str(&self) -> &str {
+        match self.entry() {
+            Some(entry) => entry.venue.as_str(),
+            None => {
+                let code_len = (self.bytes()[TAG] >> 1 & 0b111) as usize;
+                let len = (self.bytes()[TAG] >> 4 & 0b111) as usize;
+                let start = PAYLOAD + code_len;
+                // Safety: as in `code_str`
+                unsafe { std::str::from_utf8_unchecked(&self.bytes()[start..start + len]) }
+            }
+        }
+    }
+}
+
+impl<const N: usize, const M: usize> HybridIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    /// Packs `code` and `venue` inline if they fit, or interns them otherwise,
+    /// applying the truncation policy of `StaticIdNxM<N, M>` to text longer
+    /// than `N` or `M` bytes. Types without a venue take `""` as `venue`.
+    ///
+    /// # Panics
+    /// Panics if the truncation policy rejects `code` or `venue`, see
+    /// [`HybridIdNxM::try_from_str`].
+    pub fn from_str(code: &str, venue: &str) -> Self {
+        // panics after the cache lock is released, so the cache is not poisoned
+        Self::try_from_str(code, venue).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`HybridIdNxM::from_str`], but returns an error instead of
+    /// panicking if the truncation policy rejects `code` or `venue`.
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let venue = if M == 0 { "" } else { venue };
+        if let Some(id) = Self::inline(code, venue) {
+            return Ok(id);
+        }
+        let interned = StaticIdNxM::<N, M>::lock_id_cache().intern_verbatim(code.as_bytes(), venue.as_bytes())?;
+        Ok(Self::from_entry(Interned::as_static(interned)))
+    }
+
+    /// The interned id with the same text, interning it if the id is inline.
+    pub fn to_static_id(&self) -> StaticIdNxM<N, M> {
+        let id_ptr = match self.entry() {
+            Some(entry) => Interned::from_static(entry),
+            None => {
+                // inline text is within the bounds, so no policy rejects it
+                let mut cache = StaticIdNxM::<N, M>::lock_id_cache();
+                cache.intern_verbatim(self.code_str().as_bytes(), self.venue_str().as_bytes()).unwrap()
+            }
+        };
+        StaticIdNxM { id_ptr }
+    }
+}
+
+impl<const N: usize, const M: usize> From<StaticIdNxM<N, M>> for HybridIdNxM<N, M> {
+    #[inline]
+    fn from(id: StaticIdNxM<N, M>) -> Self {
+        Self::inline(id.code_str(), id.venue_str()).unwrap_or_else(|| Self::from_entry(Interned::as_static(id.id_ptr)))
+    }
+}
+
+impl<const N: usize, const M: usize> From<HybridIdNxM<N, M>> for StaticIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    #[inline]
+    fn from(id: HybridIdNxM<N, M>) -> Self {
+        id.to_static_id()
+    }
+}
+
+/// Displays the combined form in the format of `StaticIdNxM<N, M>`.
+impl<const N: usize, const M: usize> fmt::Display for HybridIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        StaticIdNxM::<N, M>::format().write(f, self.code_str(), self.venue_str())
+    }
+}
+
+impl<const N: usize, const M: usize> fmt::Debug for HybridIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+/// Serialized as the combined string, whatever the serde representation of
+/// `StaticIdNxM<N, M>`.
+impl<const N: usize, const M: usize> Serialize for HybridIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct HybridVisitor<const N: usize, const M: usize>;
+
+impl<const N: usize, const M: usize> Visitor<'_> for HybridVisitor<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    type Value = HybridIdNxM<N, M>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a combined id string such as \"AAPL{}NASDAQ\"", StaticIdNxM::<N, M>::format().separator())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let (code, venue) = StaticIdNxM::<N, M>::format().split(v).map_err(E::custom)?;
+        HybridIdNxM::try_from_str(&code, &venue).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(v)
+    }
+}
+
+impl<'de, const N: usize, const M: usize> Deserialize<'de> for HybridIdNxM<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(HybridVisitor)
+    }
+}
+
+pub type HybridId16x0 = HybridIdNxM<16, 0>;
+pub type HybridId16x16 = HybridIdNxM<16, 16>;
+pub type HybridId16x32 = HybridIdNxM<16, 32>;
+pub type HybridId16x64 = HybridIdNxM<16, 64>;
+pub type HybridId32x0 = HybridIdNxM<32, 0>;
+pub type HybridId32x16 = HybridIdNxM<32, 16>;
+pub type HybridId32x32 = HybridIdNxM<32, 32>;
+pub type HybridId32x64 = HybridIdNxM<32, 64>;
+pub type HybridId64x0 = HybridIdNxM<64, 0>;
+pub type HybridId64x16 = HybridIdNxM<64, 16>;
+pub type HybridId64x32 = HybridIdNxM<64, 32>;
+pub type HybridId64x64 = HybridIdNxM<64, 64>;
+pub type HybridId = HybridId32x32;
@@ -0,0 +1,703 @@
+use crate::collision::{Collision, CollisionDetection, CollisionTracker};
+use crate::error::{IdError, IdPart};
+use crate::format::{AtomicIdFormat, IdFormat};
+use crate::id_table::IdTableNxM;
+use crate::interned::{InternSet, Interned};
+use crate::map::IdHasher;
+use crate::normalization::Normalization;
+use crate::serde_repr::{self, AtomicSerdeRepr, SerdeRepr};
+use crate::search::IdIndex;
+use crate::symbol::Symbol;
+use crate::truncation::{Truncation, TruncationReport};
+use crate::static_id::{hash_parts, IdCoreNxM, IdEntryNxM, StaticIdNxM};
+#[cfg(feature = "rkyv")]
+use crate::static_id::ArchivedIdCoreNxM;
+use crate::validation::Validation;
+use std::borrow::Cow;
+use std::hash::BuildHasherDefault;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A code and venue within the bounds, and what the truncation policy did to them.
+type Fitted<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>, TruncationReport);
+
+/// The intern cache behind each `StaticIdNxM` type.
+///
+/// Besides the interning map itself, the cache keeps secondary indexes
+/// from each code and each venue to the ids interned with it. The indexes
+/// are updated on every new intern, so they always cover the whole universe.
+/// The code index is a sorted table, which also serves prefix and glob queries.
+///
+/// The cache also owns the type's [`Normalization`] policy, which is applied
+/// to every code and venue before it is interned or looked up, its
+/// [`Truncation`] policy for text longer than the bounds, and the
+/// [`Validation`] policies enforced by the fallible constructors.
+///
+/// With [`CollisionDetection`] on, it also remembers the input of every id
+/// and reports distinct inputs that intern to the same id.
+pub struct IdCacheNxM<const N: usize, const M: usize> {
+    /// keyed by the hash precomputed in each entry
+    map: InternSet<IdEntryNxM<N, M>, BuildHasherDefault<IdHasher>>,
+    /// the entries in intern order, and the code and venue indexes
+    index: IdIndex<IdEntryNxM<N, M>>,
+    /// the same entries, readable without the cache lock
+    table: &'static IdTableNxM<N, M>,
+    normalization: Normalization,
+    truncation: Truncation,
+    code_validation: Validation,
+    venue_validation: Validation,
+    collision_detection: CollisionDetection,
+    collisions: CollisionTracker,
+}
+
+impl<const N: usize, const M: usize> IdCacheNxM<N, M> {
+    /// An empty cache that also publishes its entries to `table`, which must
+    /// be empty. The table is owned by this cache alone: its appends are only
+    /// sound while serialized by one cache lock.
+    pub(crate) fn with_table(table: &'static IdTableNxM<N, M>) -> Self {
+        assert!(table.is_empty(), "the table of a new cache must be empty");
+        Self {
+            map: InternSet::default(),
+            index: IdIndex::default(),
+            table,
+            normalization: Normalization::default(),
+            truncation: Truncation::default(),
+            code_validation: Validation::default(),
+            venue_validation: Validation::default(),
+            collision_detection: CollisionDetection::default(),
+            collisions: CollisionTracker::default(),
+        }
+    }
+
+    /// The lock-free table of the entries of this cache.
+    #[inline]
+    pub fn table(&self) -> &'static IdTableNxM<N, M> {
+        self.table
+    }
+
+    #[inline]
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+
+    /// Sets the normalization policy. Ids interned before the change are not
+    /// re-normalized, so the policy should be set before the first intern.
+    #[inline]
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+    }
+
+    #[inline]
+    pub fn truncation(&self) -> Truncation {
+        self.truncation
+    }
+
+    /// Sets the truncation policy. Like normalization, it should be set
+    /// before the first intern.
+    #[inline]
+    pub fn set_truncation(&mut self, truncation: Truncation) {
+        self.truncation = truncation;
+    }
+
+    #[inline]
+    pub fn code_validation(&self) -> Validation {
+        self.code_validation
+    }
+
+    #[inline]
+    pub fn venue_validation(&self) -> Validation {
+        self.venue_validation
+    }
+
+    /// Sets the validation policies of the code and the venue.
+    #[inline]
+    pub fn set_validation(&mut self, code: Validation, venue: Validation) {
+        self.code_validation = code;
+        self.venue_validation = venue;
+    }
+
+    /// Applies the truncation policy to normalized `code` and `venue`.
+    #[inline]
+    fn fit<'a>(
+        &self,
+        code: &'a [u8],
+        venue: &'a [u8],
+    ) -> Result<Fitted<'a>, IdError> {
+        let (code, code_overflow) = self.truncation.fit::<N>(code, IdPart::Code)?;
+        let (venue, venue_overflow) = self.truncation.fit::<M>(venue, IdPart::Venue)?;
+        Ok((code, venue, TruncationReport { code: code_overflow, venue: venue_overflow }))
+    }
+
+    #[inline]
+    pub fn collision_detection(&self) -> CollisionDetection {
+        self.collision_detection
+    }
+
+    /// Sets the collision detection mode, see [`crate::collision`].
+    #[inline]
+    pub fn set_collision_detection(&mut self, detection: CollisionDetection) {
+        self.collision_detection = detection;
+    }
+
+    /// The collisions seen while collision detection was on.
+    #[inline]
+    pub fn collisions(&self) -> &[Collision] {
+        self.collisions.collisions()
+    }
+
+    /// Builds the core for `code` and `venue` under the normalization and truncation policies.
+    ///
+    /// # Panics
+    /// Panics if the truncation policy rejects `code` or `venue`.
+    #[inline]
+    pub fn make_core(&self, code: &[u8], venue: &[u8]) -> IdCoreNxM<N, M> {
+        let (code, venue) = (self.normalization.apply(code), self.normalization.apply(venue));
+        let (code, venue, _) = self.fit(&code, &venue).unwrap_or_else(|err| panic!("{}", err));
+        IdCoreNxM {
+            code: Symbol::from(&*code),
+            venue: Symbol::from(&*venue),
+        }
+    }
+
+    /// Like [`Self::make_core`], but also checks UTF-8 and the validation
+    /// policies, and returns an error instead of panicking.
+    pub fn try_make_core(&self, code: &[u8], venue: &[u8]) -> Result<IdCoreNxM<N, M>, IdError> {
+        let code = self.normalization.apply(code);
+        let code = std::str::from_utf8(&code).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Code })?;
+        self.code_validation.check(IdPart::Code, code)?;
+
+        let venue = self.normalization.apply(venue);
+        let venue = std::str::from_utf8(&venue).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Venue })?;
+        self.venue_validation.check(IdPart::Venue, venue)?;
+
+        let (code, venue, _) = self.fit(code.as_bytes(), venue.as_bytes())?;
+        Ok(IdCoreNxM {
+            code: Symbol::from(&*code),
+            venue: Symbol::from(&*venue),
+        })
+    }
+
+    /// Normalizes and validates `code` and `venue` and interns them.
+    #[inline]
+    pub fn try_intern_bytes(&mut self, code: &[u8], venue: &[u8]) -> Result<Interned<IdEntryNxM<N, M>>, IdError> {
+        self.try_intern_bytes_reported(code, venue).map(|(interned, _)| interned)
+    }
+
+    /// Like [`Self::try_intern_bytes`], also reporting what the truncation
+    /// policy did to `code` and `venue`.
+    pub fn try_intern_bytes_reported(
+        &mut self,
+        code: &[u8],
+        venue: &[u8],
+    ) -> Result<(Interned<IdEntryNxM<N, M>>, TruncationReport), IdError> {
+        let code = self.normalization.apply(code);
+        let code = std::str::from_utf8(&code).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Code })?;
+        self.code_validation.check(IdPart::Code, code)?;
+
+        let venue = self.normalization.apply(venue);
+        let venue = std::str::from_utf8(&venue).map_err(|_| IdError::InvalidUtf8 { part: IdPart::Venue })?;
+        self.venue_validation.check(IdPart::Venue, venue)?;
+
+        self.intern_fitted(code.as_bytes(), venue.as_bytes())
+    }
+
+    /// Normalizes `code` and `venue` and interns them.
+    ///
+    /// When the id is already interned, nothing is copied and the bytes are
+    /// hashed once.
+    ///
+    /// # Panics
+    /// Panics if the truncation policy rejects `code` or `venue`.
+    #[inline]
+    pub fn intern_bytes(&mut self, code: &[u8], venue: &[u8]) -> Interned<IdEntryNxM<N, M>> {
+        let code = self.normalization.apply(code);
+        let venue = self.normalization.apply(venue);
+        match self.intern_verbatim(&code, &venue) {
+            Ok(interned) => interned,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Interns `code` and `venue` without normalizing them, under the truncation policy.
+    #[inline]
+    pub(crate) fn intern_verbatim(&mut self, code: &[u8], venue: &[u8]) -> Result<Interned<IdEntryNxM<N, M>>, IdError> {
+        self.intern_fitted(code, venue).map(|(interned, _)| interned)
+    }
+
+    /// Interns normalized `code` and `venue` under the truncation policy.
+    fn intern_fitted(
+        &mut self,
+        code: &[u8],
+        venue: &[u8],
+    ) -> Result<(Interned<IdEntryNxM<N, M>>, TruncationReport), IdError> {
+        let (stored_code, stored_venue, report) = self.fit(code, venue)?;
+        let interned = self.intern_stored(&stored_code, &stored_venue);
+        self.observe(interned, code, venue);
+        Ok((interned, report))
+    }
+
+    /// Hands the input of `interned` to collision detection.
+    #[inline]
+    fn observe(&mut self, interned: Interned<IdEntryNxM<N, M>>, code: &[u8], venue: &[u8]) {
+        if self.collision_detection != CollisionDetection::Off {
+            let entry = Interned::as_static(interned);
+            let stored = (entry.code.as_str(), entry.venue.as_str());
+            self.collisions.observe(self.collision_detection, entry.index(), stored, (code, venue));
+        }
+    }
+
+    /// Interns the id holding exactly `code` and `venue`, see `Symbol::stored_prefix`.
+    fn intern_stored(&mut self, code: &[u8], venue: &[u8]) -> Interned<IdEntryNxM<N, M>> {
+        let hash = hash_parts(code, venue);
+        if let Some(interned) = self.find(hash, code, venue) {
+            return interned;
+        }
+        let index = u32::try_from(self.index.len()).expect("more than u32::MAX ids interned");
+        let core = IdCoreNxM {
+            code: Symbol::from(code),
+            venue: Symbol::from(venue),
+        };
+        let interned = self.map.insert_new(hash, IdEntryNxM::new(core, index, hash));
+        self.index.push(interned);
+        self.table.push(Interned::as_static(interned));
+        interned
+    }
+
+    #[inline]
+    fn find(&self, hash: u64, code: &[u8], venue: &[u8]) -> Option<Interned<IdEntryNxM<N, M>>> {
+        self.map
+            .find(hash, |entry| entry.code.as_str().as_bytes() == code && entry.venue.as_str().as_bytes() == venue)
+    }
+
+    /// Returns the interned copy of `id` without interning it.
+    #[inline]
+    pub fn get(&self, id: &IdCoreNxM<N, M>) -> Option<Interned<IdEntryNxM<N, M>>> {
+        let (code, venue) = (id.code.as_str().as_bytes(), id.venue.as_str().as_bytes());
+        self.find(hash_parts(code, venue), code, venue)
+    }
+
+    /// Normalizes `code` and `venue` and returns their interned id without
+    /// interning it. Copies nothing unless normalization or the truncation
+    /// policy changes the input. Input the policy rejects is never interned.
+    #[inline]
+    pub fn get_bytes(&self, code: &[u8], venue: &[u8]) -> Option<Interned<IdEntryNxM<N, M>>> {
+        let code = self.normalization.apply(code);
+        let venue = self.normalization.apply(venue);
+        let (code, venue, _) = self.fit(&code, &venue).ok()?;
+        self.find(hash_parts(&code, &venue), &code, &venue)
+    }
+
+    /// Returns the id interned at `index`, see [`IdEntryNxM::index`].
+    #[inline]
+    pub fn get_by_index(&self, index: u32) -> Option<Interned<IdEntryNxM<N, M>>> {
+        self.index.get(index)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// All interned ids whose code is `code`, in intern order.
+    #[inline]
+    pub fn ids_for_code(&self, code: &str) -> &[Interned<IdEntryNxM<N, M>>] {
+        self.index.ids_for_code(&self.normalization.apply_str(code))
+    }
+
+    /// All interned ids whose venue is `venue`, in intern order.
+    #[inline]
+    pub fn ids_for_venue(&self, venue: &str) -> &[Interned<IdEntryNxM<N, M>>] {
+        self.index.ids_for_venue(&self.normalization.apply_str(venue))
+    }
+
+    /// All interned ids whose code starts with `prefix`, sorted by code.
+    /// If `venue` is given, only ids on that venue are returned.
+    pub fn search_prefix(&self, prefix: &str, venue: Option<&str>) -> Vec<Interned<IdEntryNxM<N, M>>> {
+        let venue = venue.map(|venue| self.normalization.apply_str(venue));
+        self.index.search_prefix(&self.normalization.apply_str(prefix), venue.as_deref())
+    }
+
+    /// All interned ids whose code matches the glob `code_pattern`, sorted by code.
+    /// If `venue_pattern` is given, the venue has to match it as well.
+    ///
+    /// `*` matches any run of characters and `?` matches a single character.
+    pub fn search_glob(
+        &self,
+        code_pattern: &str,
+        venue_pattern: Option<&str>,
+    ) -> Vec<Interned<IdEntryNxM<N, M>>> {
+        let venue_pattern = venue_pattern.map(|pattern| self.normalization.apply_str(pattern));
+        self.index.search_glob(&self.normalization.apply_str(code_pattern), venue_pattern.as_deref())
+    }
+
+    /// The `limit` interned ids closest to `code` and `venue`, ranked by the sum
+    /// of the edit distances of code and venue, then by code and venue.
+    pub fn similar(
+        &self,
+        code: &str,
+        venue: &str,
+        limit: usize,
+    ) -> Vec<(Interned<IdEntryNxM<N, M>>, usize)> {
+        let code = self.normalization.apply_str(code);
+        let venue = self.normalization.apply_str(venue);
+        self.index.similar(&code, &venue, limit)
+    }
+}
+
+/// Gives generic code access to the global intern cache of a `StaticIdNxM` type.
+///
+/// Implemented for every `StaticIdNxM` alias exported by this crate.
+pub trait HasIdCache<const N: usize, const M: usize> {
+    fn id_cache() -> &'static Mutex<IdCacheNxM<N, M>>;
+
+    /// The text format of the type. Kept outside of the cache so that
+    /// formatting an id never takes the cache lock.
+    fn id_format() -> &'static AtomicIdFormat;
+
+    /// The serde representation of the type, `None` for the process-wide default.
+    fn id_serde_repr() -> &'static AtomicSerdeRepr;
+
+    /// The entries of the cache by intern index, readable without the cache lock.
+    fn id_table() -> &'static IdTableNxM<N, M>;
+
+    /// Locks the intern cache. The cache only panics before it changes
+    /// anything or after an intern is complete, e.g. on input rejected by
+    /// [`Truncation::Reject`] or under [`CollisionDetection::Panic`], so a
+    /// poisoned lock is recovered instead of failing every later call.
+    #[inline]
+    fn lock_id_cache() -> MutexGuard<'static, IdCacheNxM<N, M>> {
+        Self::id_cache().lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<const N: usize, const M: usize> StaticIdNxM<N, M>
+where
+    Self: HasIdCache<N, M>,
+{
+    /// The normalization policy applied to codes and venues of this type.
+    pub fn normalization() -> Normalization {
+        Self::lock_id_cache().normalization()
+    }
+
+    /// Sets the normalization policy of this type.
+    ///
+    /// Ids interned before the change keep their text, so the policy should
+    /// be set at startup, before the first id of the type is created.
+    ///
+    /// ```rust
+    /// use static_id::{Normalization, StaticId16x16};
+    ///
+    /// StaticId16x16::set_normalization(Normalization::CANONICAL);
+    /// let id = StaticId16x16::from_str(" aapl ", "nasdaq");
+    /// assert_eq!(id, StaticId16x16::from_str("AAPL", "NASDAQ"));
+    /// assert_eq!(id.to_string(), "AAPL@NASDAQ");
+    /// ```
+    pub fn set_normalization(normalization: Normalization) {
+        Self::lock_id_cache().set_normalization(normalization);
+    }
+
+    /// The truncation policy of this type.
+    pub fn truncation() -> Truncation {
+        Self::lock_id_cache().truncation()
+    }
+
+    /// Sets the truncation policy of this type, see [`Truncation`]. Like the
+    /// normalization policy, it should be set before the first id is created.
+    pub fn set_truncation(truncation: Truncation) {
+        Self::lock_id_cache().set_truncation(truncation);
+    }
+
+    /// The collision detection mode of this type.
+    pub fn collision_detection() -> CollisionDetection {
+        Self::lock_id_cache().collision_detection()
+    }
+
+    /// Turns collision detection for this type on or off, see [`crate::collision`].
+    /// It should be turned on before the first id is created.
+    pub fn set_collision_detection(detection: CollisionDetection) {
+        Self::lock_id_cache().set_collision_detection(detection);
+    }
+
+    /// Every collision seen by this type while collision detection was on, in
+    /// the order they were seen.
+    pub fn collisions() -> Vec<Collision> {
+        Self::lock_id_cache().collisions().to_vec()
+    }
+
+    /// The text format used by `Display`, `from_combined_str` and serde for this type.
+    #[inline]
+    pub fn format() -> IdFormat {
+        Self::id_format().load()
+    }
+
+    /// Sets the text format of this type.
+    ///
+    /// ```rust
+    /// use static_id::{IdFormat, StaticId64x16};
+    ///
+    /// StaticId64x16::set_format(IdFormat::new(':'));
+    /// let id = StaticId64x16::from_combined_str("AAPL:US");
+    /// assert_eq!(id.venue_str(), "US");
+    /// assert_eq!(id.to_string(), "AAPL:US");
+    /// ```
+    #[inline]
+    pub fn set_format(format: IdFormat) {
+        Self::id_format().store(format);
+    }
+
+    /// The representation used by the `Serialize` and `Deserialize` impls of
+    /// this type: its own if set, else the process-wide default.
+    #[inline]
+    pub fn serde_repr() -> SerdeRepr {
+        Self::id_serde_repr().load().unwrap_or_else(serde_repr::default_repr)
+    }
+
+    /// Sets the serde representation of this type; `None` falls back to the
+    /// process-wide default.
+    ///
+    /// ```rust
+    /// use static_id::{SerdeRepr, StaticId64x0};
+    ///
+    /// StaticId64x0::set_serde_repr(Some(SerdeRepr::Struct));
+    /// let id = StaticId64x0::from_str("BTC");
+    /// assert_eq!(serde_json::to_string(&id).unwrap(), r#"{"code":"BTC","venue":""}"#);
+    /// ```
+    #[inline]
+    pub fn set_serde_repr(repr: Option<SerdeRepr>) {
+        Self::id_serde_repr().store(repr);
+    }
+
+    /// Returns the id interned at `index`, see [`IdEntryNxM::index`].
+    /// Takes no lock.
+    #[inline]
+    pub fn from_index(index: u32) -> Option<Self> {
+        Self::id_table().get(index).map(|entry| StaticIdNxM { id_ptr: Interned::from_static(entry) })
+    }
+
+    /// Validates `code` and `venue` and interns them, like `try_from_str`.
+    /// Types without a venue take `""` as `venue`.
+    pub fn try_from_parts(code: &str, venue: &str) -> Result<Self, IdError> {
+        let id_ptr = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticIdNxM { id_ptr })
+    }
+
+    /// Like [`Self::try_from_parts`], also reporting whether the truncation
+    /// policy cut the code or the venue.
+    ///
+    /// ```rust
+    /// use static_id::{Overflow, StaticId16x32};
+    ///
+    /// let (id, report) = StaticId16x32::try_from_parts_reported("SPXW241220P05000000", "CBOE").unwrap();
+    /// assert_eq!(report.code, Overflow::Truncated);
+    /// assert_eq!(id.code_str(), "SPXW241220P05000");
+    /// ```
+    pub fn try_from_parts_reported(code: &str, venue: &str) -> Result<(Self, TruncationReport), IdError> {
+        let (id_ptr, report) = Self::lock_id_cache()
+            .try_intern_bytes_reported(code.as_bytes(), venue.as_bytes())?;
+        Ok((StaticIdNxM { id_ptr }, report))
+    }
+
+    /// Resolves an archived core, e.g. one read from a memory-mapped archive,
+    /// to the interned id, interning it if needed. The archived bytes are not
+    /// trusted: they are normalized and validated like `try_from_bytes`.
+    ///
+    /// ```rust
+    /// use static_id::{IdCore, StaticId};
+    ///
+    /// let id = StaticId::from_str("AAPL", "NASDAQ");
+    /// let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(id.get_id()).unwrap();
+    ///
+    /// let archived = rkyv::access::<rkyv::Archived<IdCore>, rkyv::rancor::Error>(&bytes).unwrap();
+    /// assert_eq!(archived.code.as_bytes(), b"AAPL");
+    /// assert_eq!(StaticId::try_from_archived(archived).unwrap(), id);
+    /// ```
+    #[cfg(feature = "rkyv")]
+    pub fn try_from_archived(core: &ArchivedIdCoreNxM<N, M>) -> Result<Self, IdError> {
+        let id_ptr = Self::lock_id_cache().try_intern_bytes(core.code.as_bytes(), core.venue.as_bytes())?;
+        Ok(StaticIdNxM { id_ptr })
+    }
+
+    /// Resolves an archived core to the interned id without interning it,
+    /// see [`Self::lookup`].
+    #[cfg(feature = "rkyv")]
+    pub fn lookup_archived(core: &ArchivedIdCoreNxM<N, M>) -> Option<Self> {
+        let cache = Self::lock_id_cache();
+        cache.get_bytes(core.code.as_bytes(), core.venue.as_bytes()).map(|id_ptr| StaticIdNxM { id_ptr })
+    }
+
+    /// Interns a raw core, e.g. one read from a shared-memory region, see
+    /// [`IdCoreNxM::try_ref_from_bytes`]. The core is normalized and validated
+    /// like `try_from_bytes`.
+    pub fn try_from_core_bytes(bytes: &[u8]) -> Result<Self, IdError> {
+        let core = IdCoreNxM::<N, M>::try_ref_from_bytes(bytes)?;
+        Self::try_from_parts(core.code.as_str(), core.venue.as_str())
+    }
+
+    /// Parses `combined` in the given `format` and interns it.
+    ///
+    /// Unlike the fallible parser, keeps invalid escape sequences and later
+    /// separators as written, see [`IdFormat::split_lenient`].
+    ///
+    /// # Panics
+    /// Panics if `combined` does not contain the separator of `format`.
+    pub fn from_combined_str_with(combined: &str, format: IdFormat) -> Self {
+        let (code, venue) = format.split_lenient(combined).unwrap();
+        let id_ptr = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
+        StaticIdNxM { id_ptr }
+    }
+
+    /// Parses `combined` in the given `format`, validates it and interns it.
+    pub fn try_from_combined_str_with(combined: &str, format: IdFormat) -> Result<Self, IdError> {
+        let (code, venue) = format.split(combined)?;
+        let id_ptr = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticIdNxM { id_ptr })
+    }
+
+    /// Sets the validation policies enforced by the fallible constructors
+    /// and by `Deserialize`.
+    ///
+    /// ```rust
+    /// use static_id::{CharClasses, IdError, StaticId16x32, Validation};
+    ///
+    /// let ascii = Validation {
+    ///     allowed: CharClasses::PRINTABLE_ASCII,
+    ///     min_len: 1,
+    ///     custom: None,
+    /// };
+    /// StaticId16x32::set_validation(ascii, ascii);
+    ///
+    /// assert!(StaticId16x32::try_from_str("AAPL", "NASDAQ").is_ok());
+    /// assert!(matches!(
+    ///     StaticId16x32::try_from_str("AAPL\n", "NASDAQ"),
+    ///     Err(IdError::DisallowedChar { ch: '\n', .. })
+    /// ));
+    /// ```
+    pub fn set_validation(code: Validation, venue: Validation) {
+        Self::lock_id_cache().set_validation(code, venue);
+    }
+
+    /// The validation policy of the code of this type.
+    pub fn code_validation() -> Validation {
+        Self::lock_id_cache().code_validation()
+    }
+
+    /// The validation policy of the venue of this type.
+    pub fn venue_validation() -> Validation {
+        Self::lock_id_cache().venue_validation()
+    }
+
+    /// Returns the id for `code` and `venue` if it has already been interned,
+    /// without interning it otherwise. Types without a venue take `""` as `venue`.
+    ///
+    /// ```rust
+    /// use static_id::StaticId;
+    ///
+    /// let id = StaticId::from_str("AAPL", "NASDAQ");
+    /// assert_eq!(StaticId::lookup("AAPL", "NASDAQ"), Some(id));
+    /// assert_eq!(StaticId::lookup("APPL", "NASDAQ"), None);
+    /// ```
+    pub fn lookup(code: &str, venue: &str) -> Option<Self> {
+        let cache = Self::lock_id_cache();
+        cache.get_bytes(code.as_bytes(), venue.as_bytes()).map(|id_ptr| StaticIdNxM { id_ptr })
+    }
+
+    /// The `limit` interned ids most similar to `code` and `venue`, best first,
+    /// together with their edit distance.
+    ///
+    /// The distance is the Levenshtein distance of the codes plus that of the
+    /// venues, with ASCII letters compared case-insensitively.
+    pub fn similar(code: &str, venue: &str, limit: usize) -> Vec<(Self, usize)> {
+        let cache = Self::lock_id_cache();
+        cache
+            .similar(code, venue, limit)
+            .into_iter()
+            .map(|(id_ptr, distance)| (StaticIdNxM { id_ptr }, distance))
+            .collect()
+    }
+
+    /// Looks up an id without interning it, and suggests up to `limit`
+    /// "did you mean" candidates if it is unknown.
+    ///
+    /// ```rust
+    /// use static_id::StaticId;
+    ///
+    /// let aapl = StaticId::from_str("AAPL", "NASDAQ");
+    /// let _ = StaticId::from_str("MSFT", "NASDAQ");
+    ///
+    /// assert_eq!(StaticId::lookup_or_suggest("AAPL", "NASDAQ", 3), Ok(aapl));
+    /// let suggestions = StaticId::lookup_or_suggest("APPL", "NASDAQ", 3).unwrap_err();
+    /// assert_eq!(suggestions[0], aapl);
+    /// ```
+    pub fn lookup_or_suggest(code: &str, venue: &str, limit: usize) -> Result<Self, Vec<Self>> {
+        match Self::lookup(code, venue) {
+            Some(id) => Ok(id),
+            None => Err(Self::similar(code, venue, limit).into_iter().map(|(id, _)| id).collect()),
+        }
+    }
+
+    /// Every interned id traded on `venue`, in intern order.
+    ///
+    /// ```rust
+    /// use static_id::StaticId;
+    ///
+    /// let samsung = StaticId::from_str("005930", "KRX");
+    /// let hynix = StaticId::from_str("000660", "KRX");
+    /// assert_eq!(StaticId::ids_for_venue("KRX"), vec![samsung, hynix]);
+    /// ```
+    pub fn ids_for_venue(venue: &str) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.ids_for_venue(venue).iter().map(|&id_ptr| StaticIdNxM { id_ptr }).collect()
+    }
+
+    /// Every interned id with the given `code`, one per venue, in intern order.
+    pub fn ids_for_code(code: &str) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.ids_for_code(code).iter().map(|&id_ptr| StaticIdNxM { id_ptr }).collect()
+    }
+
+    /// Every interned id whose code starts with `prefix`, sorted by code,
+    /// optionally restricted to one venue.
+    ///
+    /// ```rust
+    /// use static_id::StaticId;
+    ///
+    /// let spy = StaticId::from_str("SPY", "ARCA");
+    /// let spyg = StaticId::from_str("SPYG", "ARCA");
+    /// let _ = StaticId::from_str("SPY", "BATS");
+    /// assert_eq!(StaticId::search_prefix("SPY", Some("ARCA")), vec![spy, spyg]);
+    /// ```
+    pub fn search_prefix(prefix: &str, venue: Option<&str>) -> Vec<Self> {
+        let cache = Self::lock_id_cache();
+        cache.search_prefix(prefix, venue).into_iter().map(|id_ptr| StaticIdNxM { id_ptr }).collect()
+    }
+
+    /// Every interned id matching a glob query, sorted by code.
+    ///
+    /// The query is either a code pattern, such as `"ES*"`, or a code pattern
+    /// and a venue pattern joined by the separator of the type's format, such
+    /// as `"ES*@CME"`. `*` matches any run of characters and `?` matches a
+    /// single character. A separator inside the code pattern is escaped as in
+    /// the combined form, e.g. `r"A\@B*@X"`.
+    ///
+    /// ```rust
+    /// use static_id::StaticId;
+    ///
+    /// let esz4 = StaticId::from_str("ESZ4", "CME");
+    /// let _ = StaticId::from_str("ESZ4", "EUREX");
+    /// let _ = StaticId::from_str("NQZ4", "CME");
+    /// assert_eq!(StaticId::search_glob("ES*@CME"), vec![esz4]);
+    /// ```
+    pub fn search_glob(query: &str) -> Vec<Self> {
+        let (code_pattern, venue_pattern) = Self::format().split_query(query);
+        let cache = Self::lock_id_cache();
+        cache
+            .search_glob(&code_pattern, venue_pattern.as_deref())
+            .into_iter()
+            .map(|id_ptr| StaticIdNxM { id_ptr })
+            .collect()
+    }
+}
@@ -0,0 +1,105 @@
+//! The lock-free index of interned entries behind `StaticIdNxM::from_index`.
+use crate::static_id::IdEntryNxM;
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicPtr, AtomicU32, Ordering};
+
+/// log2 of the size of the first segment.
+const FIRST_SEGMENT_BITS: u32 = 6;
+/// Enough doubling segments to cover every `u32` index.
+const SEGMENTS: usize = 27;
+
+/// Maps each intern index to its entry, readable without any lock.
+///
+/// The table is append-only and made of segments that double in size, so
+/// entries never move. Segment `k` holds the indices from `64 * (2^k - 1)`
+/// on. Appends are serialized by the intern cache lock; an entry is published
+/// with a release store of the length, and readers only look below the
+/// length they acquired.
+pub struct IdTableNxM<const N: usize, const M: usize> {
+    segments: [AtomicPtr<AtomicPtr<IdEntryNxM<N, M>>>; SEGMENTS],
+    len: AtomicU32,
+}
+
+impl<const N: usize, const M: usize> IdTableNxM<N, M> {
+    pub const fn new() -> Self {
+        Self {
+            segments: [const { AtomicPtr::new(null_mut()) }; SEGMENTS],
+            len: AtomicU32::new(0),
+        }
+    }
+
+    /// The segment of `index` and the position of `index` in it.
+    #[inline]
+    fn locate(index: u32) -> (usize, usize) {
+        let biased = (index as u64 >> FIRST_SEGMENT_BITS) + 1;
+        let segment = biased.ilog2();
+        let start = ((1u64 << segment) - 1) << FIRST_SEGMENT_BITS;
+        (segment as usize, (index as u64 - start) as usize)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len.load(Ordering::Acquire) as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The entry at `index`, if one has been published.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<&'static IdEntryNxM<N, M>> {
+        if index >= self.len.load(Ordering::Acquire) {
+            return None;
+        }
+        let (segment, offset) = Self::locate(index);
+        let segment = self.segments[segment].load(Ordering::Acquire);
+        // Safety: the segment and the slot were written before `len` was
+        // released past `index`, and neither is ever freed
+        let entry = unsafe { (*segment.add(offset)).load(Ordering::Relaxed) };
+        Some(unsafe { &*entry })
+    }
+
+    /// Appends `entry`, whose index must be the current length. Appends must
+    /// not run concurrently; the intern cache lock serializes them.
+    pub(crate) fn push(&self, entry: &'static IdEntryNxM<N, M>) {
+        let index = self.len.load(Ordering::Relaxed);
+        debug_assert_eq!(index, entry.index());
+        let (segment, offset) = Self::locate(index);
+        if offset == 0 {
+            let slots: Box<[AtomicPtr<IdEntryNxM<N, M>>]> =
+                (0..1usize << (segment as u32 + FIRST_SEGMENT_BITS)).map(|_| AtomicPtr::new(null_mut())).collect();
+            let slots = Box::leak(slots).as_mut_ptr();
+            self.segments[segment].store(slots, Ordering::Release);
+        }
+        let slots = self.segments[segment].load(Ordering::Relaxed);
+        // Safety: `offset` is within the segment allocated for `index`
+        unsafe { (*slots.add(offset)).store(entry as *const _ as *mut _, Ordering::Relaxed) };
+        self.len.store(index + 1, Ordering::Release);
+    }
+}
+
+impl<const N: usize, const M: usize> Default for IdTableNxM<N, M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        type Table = IdTableNxM<16, 0>;
+        assert_eq!(Table::locate(0), (0, 0));
+        assert_eq!(Table::locate(63), (0, 63));
+        assert_eq!(Table::locate(64), (1, 0));
+        assert_eq!(Table::locate(191), (1, 127));
+        assert_eq!(Table::locate(192), (2, 0));
+        let (segment, offset) = Table::locate(u32::MAX);
+        assert!(segment < SEGMENTS);
+        assert!(offset < 1 << (segment as u32 + FIRST_SEGMENT_BITS));
+    }
+}
@@ -0,0 +1,334 @@
+//! Hash-consing for arbitrary `Hash + Eq` types.
+//!
+//! An [`Interner<T>`] keeps one leaked copy of every distinct value it is
+//! given and hands out [`Interned<T>`] handles to it. A handle is a single
+//! `&'static T`: equal values intern to the same allocation, so handles
+//! compare and hash by address, 8 bytes whatever the size of `T`, and copy
+//! for free. `StaticIdNxM` is such a handle to its interned entry.
+//!
+//! A type gets a global interner by implementing [`Intern`] with a `static`:
+//!
+//! ```rust
+//! use static_id::interned::{Intern, Interned, Interner};
+//!
+//! #[derive(Debug, PartialEq, Eq, Hash)]
+//! struct OptionSpec {
+//!     underlying: String,
+//!     strike: u64,
+//! }
+//!
+//! impl Intern for OptionSpec {
+//!     fn interner() -> &'static Interner<Self> {
+//!         static INTERNER: Interner<OptionSpec> = Interner::new();
+//!         &INTERNER
+//!     }
+//! }
+//!
+//! let a = Interned::new(OptionSpec { underlying: "AAPL".into(), strike: 150 });
+//! let b = Interned::new(OptionSpec { underlying: "AAPL".into(), strike: 150 });
+//! assert_eq!(a, b);
+//! assert_eq!(Interned::as_ptr(a), Interned::as_ptr(b));
+//! assert_eq!(a.strike, 150);
+//! assert_eq!(OptionSpec::interner().len(), 1);
+//! ```
+//!
+//! Interned values are never freed.
+use hashbrown::HashTable;
+use rustc_hash::FxBuildHasher;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+/// A handle to a value interned by an [`Interner`].
+///
+/// Two handles from the same interner are equal exactly when their values
+/// are, and the comparison only looks at the addresses.
+pub struct Interned<T: 'static> {
+    ptr: &'static T,
+}
+
+impl<T: 'static> Interned<T> {
+    /// Interns `value` in the global interner of `T`.
+    #[inline]
+    pub fn new(value: T) -> Self
+    where
+        T: Intern,
+    {
+        T::interner().intern(value)
+    }
+
+    /// Wraps a value leaked by an `InternSet`, the only place allowed to.
+    #[inline]
+    pub(crate) fn from_static(ptr: &'static T) -> Self {
+        Self { ptr }
+    }
+
+    /// The interned value, for as long as the program runs. An associated
+    /// function, like the others here, so it never shadows a method of `T`.
+    #[inline]
+    pub const fn as_static(this: Self) -> &'static T {
+        this.ptr
+    }
+
+    /// The address of the interned value, unique per distinct value.
+    #[inline]
+    pub const fn as_ptr(this: Self) -> *const T {
+        this.ptr
+    }
+}
+
+impl<T: 'static> Clone for Interned<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for Interned<T> {}
+
+impl<T: 'static> PartialEq for Interned<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.ptr, other.ptr)
+    }
+}
+
+impl<T: 'static> Eq for Interned<T> {}
+
+/// Hashes the address, which is only meaningful within one process.
+impl<T: 'static> Hash for Interned<T> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::ptr::hash(self.ptr, state)
+    }
+}
+
+impl<T: 'static> Deref for Interned<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        self.ptr
+    }
+}
+
+impl<T: 'static> AsRef<T> for Interned<T> {
+    #[inline]
+    fn as_ref(&self) -> &T {
+        self.ptr
+    }
+}
+
+impl<T: fmt::Display + 'static> fmt::Display for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self.ptr, f)
+    }
+}
+
+impl<T: fmt::Debug + 'static> fmt::Debug for Interned<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.ptr, f)
+    }
+}
+
+/// Serializes the value itself.
+impl<T: Serialize + 'static> Serialize for Interned<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.ptr.serialize(serializer)
+    }
+}
+
+/// Deserializes a value and interns it in the global interner of `T`.
+impl<'de, T: Intern + Deserialize<'de>> Deserialize<'de> for Interned<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Self::new)
+    }
+}
+
+/// Gives a type a global [`Interner`], see the [module docs](self).
+pub trait Intern: Hash + Eq + Send + Sync + Sized + 'static {
+    fn interner() -> &'static Interner<Self>;
+
+    /// Interns `self` in [`Self::interner`].
+    #[inline]
+    fn intern(self) -> Interned<Self> {
+        Self::interner().intern(self)
+    }
+}
+
+/// The set of values leaked by an interner, without a lock.
+///
+/// Values are hashed with `S`. Besides interning by value, the set can be
+/// searched and filled by a precomputed hash, which lets a caller look a
+/// value up from borrowed parts without building it first. Such a hash must
+/// be the one `S` computes for the value.
+pub struct InternSet<T: 'static, S = FxBuildHasher> {
+    table: HashTable<&'static T>,
+    hasher: S,
+}
+
+impl<T: 'static> InternSet<T> {
+    pub const fn new() -> Self {
+        Self::with_hasher(FxBuildHasher)
+    }
+}
+
+impl<T: 'static, S: Default> Default for InternSet<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T: 'static, S> InternSet<T, S> {
+    pub const fn with_hasher(hasher: S) -> Self {
+        Self {
+            table: HashTable::new(),
+            hasher,
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// The interned value with hash `hash` for which `eq` holds.
+    #[inline]
+    pub fn find(&self, hash: u64, mut eq: impl FnMut(&T) -> bool) -> Option<Interned<T>> {
+        self.table.find(hash, |value| eq(value)).map(|&ptr| Interned::from_static(ptr))
+    }
+
+    /// Iterates over the interned values in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = Interned<T>> + '_ {
+        self.table.iter().map(|&ptr| Interned::from_static(ptr))
+    }
+}
+
+impl<T: Hash + Eq + 'static, S: BuildHasher> InternSet<T, S> {
+    /// The interned copy of `value`, if any.
+    #[inline]
+    pub fn get<Q>(&self, value: &Q) -> Option<Interned<T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.find(self.hasher.hash_one(value), |interned| interned.borrow() == value)
+    }
+
+    /// Returns the interned copy of `value`, leaking `value` if there is none yet.
+    pub fn intern(&mut self, value: T) -> Interned<T> {
+        let hash = self.hasher.hash_one(&value);
+        match self.find(hash, |interned| *interned == value) {
+            Some(interned) => interned,
+            None => self.insert_new(hash, value),
+        }
+    }
+
+    /// Like [`Self::intern`], but only clones `value` when it is not interned yet.
+    pub fn intern_borrowed<Q>(&mut self, value: &Q) -> Interned<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        let hash = self.hasher.hash_one(value);
+        match self.find(hash, |interned| interned.borrow() == value) {
+            Some(interned) => interned,
+            None => self.insert_new(hash, value.to_owned()),
+        }
+    }
+
+    /// Leaks `value`, which must not be interned yet, under its hash `hash`.
+    /// Kept to the crate: a duplicate or a wrong hash would give equal values
+    /// two different pointers.
+    pub(crate) fn insert_new(&mut self, hash: u64, value: T) -> Interned<T> {
+        debug_assert_eq!(hash, self.hasher.hash_one(&value));
+        let ptr: &'static T = Box::leak(Box::new(value));
+        let hasher = &self.hasher;
+        self.table.insert_unique(hash, ptr, |value| hasher.hash_one(value));
+        Interned::from_static(ptr)
+    }
+}
+
+/// A thread-safe [`InternSet`], usable in a `static`.
+pub struct Interner<T: 'static, S = FxBuildHasher> {
+    set: Mutex<InternSet<T, S>>,
+}
+
+impl<T: 'static> Interner<T> {
+    pub const fn new() -> Self {
+        Self::with_hasher(FxBuildHasher)
+    }
+}
+
+impl<T: 'static, S: Default> Default for Interner<T, S> {
+    fn default() -> Self {
+        Self::with_hasher(S::default())
+    }
+}
+
+impl<T: 'static, S> Interner<T, S> {
+    pub const fn with_hasher(hasher: S) -> Self {
+        Self {
+            set: Mutex::new(InternSet::with_hasher(hasher)),
+        }
+    }
+
+    /// Locks the set. A panic while the lock is held, e.g. in a `Hash` or
+    /// `Eq` impl, leaves the set consistent, so a poisoned lock is recovered.
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, InternSet<T, S>> {
+        self.set.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+}
+
+impl<T: Hash + Eq + 'static, S: BuildHasher> Interner<T, S> {
+    /// See [`InternSet::get`].
+    #[inline]
+    pub fn get<Q>(&self, value: &Q) -> Option<Interned<T>>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.lock().get(value)
+    }
+
+    /// See [`InternSet::intern`].
+    #[inline]
+    pub fn intern(&self, value: T) -> Interned<T> {
+        self.lock().intern(value)
+    }
+
+    /// See [`InternSet::intern_borrowed`].
+    #[inline]
+    pub fn intern_borrowed<Q>(&self, value: &Q) -> Interned<T>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = T> + ?Sized,
+    {
+        self.lock().intern_borrowed(value)
+    }
+}
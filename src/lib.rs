@@ -47,16 +47,43 @@
 //!
 pub mod static_id;
 pub mod symbol;
+pub mod atomic;
+pub mod collision;
+pub mod compact;
+pub mod dynamic;
+pub mod error;
+pub mod fingerprint;
+pub mod format;
+pub mod hybrid;
+pub mod id_cache;
+pub mod id_table;
+pub mod interned;
+pub mod map;
+pub mod meta;
+pub mod normalization;
+mod search;
+pub mod serde_repr;
+pub mod shared;
+pub mod stream;
+pub mod truncation;
+pub mod validation;
 
 pub use symbol::Symbol;
 pub use crate::static_id::*;
+pub use id_cache::{HasIdCache, IdCacheNxM};
+pub use error::{IdError, IdPart};
+pub use format::{IdFormat, AtomicIdFormat};
+pub use normalization::Normalization;
+pub use serde_repr::SerdeRepr;
+pub use truncation::{Overflow, Truncation, TruncationReport};
+pub use validation::{CharClasses, Predicate, Validation};
 
 #[cfg(test)]
 mod tests {
     use crate::StaticId;
+    use crate::interned::Interned;
     use std::mem::size_of;
     use std::collections::HashMap;
-    use serde_json;
 
     #[test]
     fn test_serde() {
@@ -89,7 +116,7 @@ mod tests {
         let id1 = StaticId::from_str("ABC", "NYSE");
         let id2 = StaticId::from_str("ABC", "NYSE");
 
-        assert!(std::ptr::eq(id1.id_ptr, id2.id_ptr));
+        assert!(std::ptr::eq(Interned::as_ptr(id1.id_ptr), Interned::as_ptr(id2.id_ptr)));
     }
 
     #[test]
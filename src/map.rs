@@ -0,0 +1,211 @@
+//! A concurrent map keyed by interned ids.
+//!
+//! [`IdMapNxM`] splits its entries into shards, each behind its own `Mutex`.
+//! The shard of an id is chosen from its intern index and, inside a shard, ids
+//! are hashed by the hash precomputed at intern time, so no operation reads
+//! the text of an id. Threads working on ids of different shards never wait
+//! for each other.
+//!
+//! ```rust
+//! use static_id::map::IdMap;
+//! use static_id::StaticId;
+//!
+//! let map = IdMap::new();
+//! let aapl = StaticId::from_str("AAPL", "NASDAQ");
+//!
+//! map.insert(aapl, 100);
+//! map.entry(aapl, |entry| *entry.or_insert(0) += 1);
+//! assert_eq!(map.get(&aapl), Some(101));
+//! assert_eq!(map.remove(&aapl), Some(101));
+//! assert!(map.is_empty());
+//! ```
+use crate::static_id::StaticIdNxM;
+use std::collections::hash_map::{self, HashMap};
+use std::hash::{BuildHasherDefault, Hasher};
+use std::sync::{Mutex, MutexGuard};
+
+/// Passes the precomputed hash of a `StaticIdNxM` through.
+#[derive(Default)]
+pub struct IdHasher(u64);
+
+impl Hasher for IdHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write_u64(&mut self, hash: u64) {
+        self.0 = hash;
+    }
+
+    /// Not used by `StaticIdNxM`, which only writes its precomputed hash.
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0.rotate_left(5) ^ u64::from(byte)).wrapping_mul(0x517c_c1b7_2722_0a95);
+        }
+    }
+}
+
+/// The map of one shard.
+pub type IdShard<const N: usize, const M: usize, V> = HashMap<StaticIdNxM<N, M>, V, BuildHasherDefault<IdHasher>>;
+
+/// A sharded concurrent map keyed by `StaticIdNxM`, see the [module docs](self).
+pub struct IdMapNxM<const N: usize, const M: usize, V> {
+    shards: Box<[Mutex<IdShard<N, M, V>>]>,
+    mask: usize,
+}
+
+impl<const N: usize, const M: usize, V> IdMapNxM<N, M, V> {
+    /// A map with 4 shards per available CPU.
+    pub fn new() -> Self {
+        let cpus = std::thread::available_parallelism().map_or(1, usize::from);
+        Self::with_shards(cpus * 4)
+    }
+
+    /// A map with `shards` shards, rounded up to a power of two.
+    pub fn with_shards(shards: usize) -> Self {
+        let shards = shards.max(1).next_power_of_two();
+        Self {
+            shards: (0..shards).map(|_| Mutex::default()).collect(),
+            mask: shards - 1,
+        }
+    }
+
+    /// The shard holding `id`.
+    #[inline]
+    pub fn shard(&self, id: &StaticIdNxM<N, M>) -> &Mutex<IdShard<N, M, V>> {
+        &self.shards[id.index() as usize & self.mask]
+    }
+
+    /// All shards, for callers that lock them directly.
+    #[inline]
+    pub fn shards(&self) -> &[Mutex<IdShard<N, M, V>>] {
+        &self.shards
+    }
+
+    #[inline]
+    fn lock(&self, id: &StaticIdNxM<N, M>) -> MutexGuard<'_, IdShard<N, M, V>> {
+        self.shard(id).lock().unwrap()
+    }
+
+    /// A clone of the value of `id`.
+    #[inline]
+    pub fn get(&self, id: &StaticIdNxM<N, M>) -> Option<V>
+    where
+        V: Clone,
+    {
+        self.lock(id).get(id).cloned()
+    }
+
+    /// Calls `f` on the value of `id` under the shard lock.
+    #[inline]
+    pub fn get_with<R>(&self, id: &StaticIdNxM<N, M>, f: impl FnOnce(&V) -> R) -> Option<R> {
+        self.lock(id).get(id).map(f)
+    }
+
+    #[inline]
+    pub fn contains_key(&self, id: &StaticIdNxM<N, M>) -> bool {
+        self.lock(id).contains_key(id)
+    }
+
+    /// Inserts `value` for `id` and returns the previous value.
+    #[inline]
+    pub fn insert(&self, id: StaticIdNxM<N, M>, value: V) -> Option<V> {
+        self.lock(&id).insert(id, value)
+    }
+
+    #[inline]
+    pub fn remove(&self, id: &StaticIdNxM<N, M>) -> Option<V> {
+        self.lock(id).remove(id)
+    }
+
+    /// Calls `f` on the entry of `id` under the shard lock.
+    #[inline]
+    pub fn entry<R>(&self, id: StaticIdNxM<N, M>, f: impl FnOnce(hash_map::Entry<'_, StaticIdNxM<N, M>, V>) -> R) -> R {
+        f(self.lock(&id).entry(id))
+    }
+
+    /// Number of entries, summed shard by shard.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.lock().unwrap().len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.lock().unwrap().is_empty())
+    }
+
+    /// Calls `f` on every entry, locking one shard at a time.
+    pub fn for_each(&self, mut f: impl FnMut(&StaticIdNxM<N, M>, &V)) {
+        for shard in self.shards.iter() {
+            for (id, value) in shard.lock().unwrap().iter() {
+                f(id, value);
+            }
+        }
+    }
+
+    /// Calls `f` on every entry, locking one shard at a time.
+    pub fn for_each_mut(&self, mut f: impl FnMut(&StaticIdNxM<N, M>, &mut V)) {
+        for shard in self.shards.iter() {
+            for (id, value) in shard.lock().unwrap().iter_mut() {
+                f(id, value);
+            }
+        }
+    }
+
+    /// Keeps the entries for which `f` returns `true`, one shard at a time.
+    pub fn retain(&self, mut f: impl FnMut(&StaticIdNxM<N, M>, &mut V) -> bool) {
+        for shard in self.shards.iter() {
+            shard.lock().unwrap().retain(|id, value| f(id, value));
+        }
+    }
+
+    /// A copy of all entries. Each shard is copied atomically, but not the map as a whole.
+    pub fn snapshot(&self) -> Vec<(StaticIdNxM<N, M>, V)>
+    where
+        V: Clone,
+    {
+        let mut entries = Vec::new();
+        self.for_each(|id, value| entries.push((*id, value.clone())));
+        entries
+    }
+
+    pub fn clear(&self) {
+        for shard in self.shards.iter() {
+            shard.lock().unwrap().clear();
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, V> Default for IdMapNxM<N, M, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const M: usize, V> IntoIterator for IdMapNxM<N, M, V> {
+    type Item = (StaticIdNxM<N, M>, V);
+    type IntoIter = std::iter::FlatMap<
+        std::vec::IntoIter<Mutex<IdShard<N, M, V>>>,
+        IdShard<N, M, V>,
+        fn(Mutex<IdShard<N, M, V>>) -> IdShard<N, M, V>,
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.shards.into_vec().into_iter().flat_map(|shard| shard.into_inner().unwrap())
+    }
+}
+
+pub type IdMap16x0<V> = IdMapNxM<16, 0, V>;
+pub type IdMap16x16<V> = IdMapNxM<16, 16, V>;
+pub type IdMap16x32<V> = IdMapNxM<16, 32, V>;
+pub type IdMap16x64<V> = IdMapNxM<16, 64, V>;
+pub type IdMap32x0<V> = IdMapNxM<32, 0, V>;
+pub type IdMap32x16<V> = IdMapNxM<32, 16, V>;
+pub type IdMap32x32<V> = IdMapNxM<32, 32, V>;
+pub type IdMap32x64<V> = IdMapNxM<32, 64, V>;
+pub type IdMap64x0<V> = IdMapNxM<64, 0, V>;
+pub type IdMap64x16<V> = IdMapNxM<64, 16, V>;
+pub type IdMap64x32<V> = IdMapNxM<64, 32, V>;
+pub type IdMap64x64<V> = IdMapNxM<64, 64, V>;
+pub type IdMap<V> = IdMap32x32<V>;
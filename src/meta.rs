@@ -0,0 +1,297 @@
+//! Ids carrying user metadata, such as the static data of an instrument.
+//!
+//! A [`MetaRegistryNxM`] attaches a payload to ids once, at registration, and
+//! hands out [`MetaIdNxM`] handles: 8-byte pointers to an interned entry that
+//! holds both the id and its payload, so [`MetaIdNxM::meta`] is a single
+//! dereference. Looking an id up again, by id or by string, returns the same
+//! handle.
+//!
+//! ```rust
+//! use static_id::meta::MetaRegistry;
+//!
+//! struct InstrumentInfo {
+//!     tick_size: f64,
+//!     currency: &'static str,
+//! }
+//!
+//! static INSTRUMENTS: MetaRegistry<InstrumentInfo> = MetaRegistry::new();
+//!
+//! let aapl = INSTRUMENTS
+//!     .register_str("AAPL", "NASDAQ", InstrumentInfo { tick_size: 0.01, currency: "USD" })
+//!     .unwrap();
+//! assert_eq!(aapl.meta().currency, "USD");
+//! assert_eq!(INSTRUMENTS.lookup("AAPL", "NASDAQ"), Some(aapl));
+//!
+//! // metadata is set once
+//! let again = INSTRUMENTS.register_str("AAPL", "NASDAQ", InstrumentInfo { tick_size: 1.0, currency: "KRW" });
+//! assert_eq!(again.unwrap_err().registered().unwrap().meta().tick_size, 0.01);
+//! ```
+use crate::error::IdError;
+use crate::id_cache::HasIdCache;
+use crate::static_id::StaticIdNxM;
+use rustc_hash::{FxBuildHasher, FxHashMap};
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ptr::eq as ptr_eq;
+use std::sync::RwLock;
+
+/// The interned allocation a `MetaIdNxM` points to.
+pub struct MetaEntryNxM<const N: usize, const M: usize, T: 'static> {
+    id: StaticIdNxM<N, M>,
+    meta: T,
+}
+
+/// An id with metadata, see the [module docs](self).
+pub struct MetaIdNxM<const N: usize, const M: usize, T: 'static> {
+    entry: &'static MetaEntryNxM<N, M, T>,
+}
+
+impl<const N: usize, const M: usize, T> MetaIdNxM<N, M, T> {
+    /// The metadata set at registration.
+    #[inline]
+    pub fn meta(&self) -> &'static T {
+        &self.entry.meta
+    }
+
+    #[inline]
+    pub fn id(&self) -> StaticIdNxM<N, M> {
+        self.entry.id
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn code_str(&self) -> &'static str {
+        self.entry.id.id_ptr.code.as_str()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn venue_str(&self) -> &'static str {
+        self.entry.id.id_ptr.venue.as_str()
+    }
+}
+
+impl<const N: usize, const M: usize, T> Clone for MetaIdNxM<N, M, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<const N: usize, const M: usize, T> Copy for MetaIdNxM<N, M, T> {}
+
+impl<const N: usize, const M: usize, T> PartialEq for MetaIdNxM<N, M, T> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr_eq(self.entry, other.entry)
+    }
+}
+
+impl<const N: usize, const M: usize, T> Eq for MetaIdNxM<N, M, T> {}
+
+/// Hashes like the `StaticIdNxM` it wraps.
+impl<const N: usize, const M: usize, T> Hash for MetaIdNxM<N, M, T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.entry.id.hash(state);
+    }
+}
+
+impl<const N: usize, const M: usize, T> From<MetaIdNxM<N, M, T>> for StaticIdNxM<N, M> {
+    #[inline]
+    fn from(id: MetaIdNxM<N, M, T>) -> Self {
+        id.id()
+    }
+}
+
+impl<const N: usize, const M: usize, T> fmt::Display for MetaIdNxM<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.entry.id, f)
+    }
+}
+
+impl<const N: usize, const M: usize, T> fmt::Debug for MetaIdNxM<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.entry.id, f)
+    }
+}
+
+/// Why [`MetaRegistryNxM::register_str`] registered nothing.
+pub enum RegisterError<const N: usize, const M: usize, T: 'static> {
+    /// The code or venue is rejected by the policies of the id type.
+    Invalid(IdError),
+    /// The id is already registered, with this handle.
+    AlreadyRegistered(MetaIdNxM<N, M, T>),
+}
+
+impl<const N: usize, const M: usize, T> RegisterError<N, M, T> {
+    /// The existing handle, if the id is already registered.
+    #[inline]
+    pub fn registered(&self) -> Option<MetaIdNxM<N, M, T>> {
+        match self {
+            RegisterError::Invalid(_) => None,
+            RegisterError::AlreadyRegistered(id) => Some(*id),
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, T> From<IdError> for RegisterError<N, M, T> {
+    #[inline]
+    fn from(err: IdError) -> Self {
+        RegisterError::Invalid(err)
+    }
+}
+
+impl<const N: usize, const M: usize, T> Clone for RegisterError<N, M, T> {
+    fn clone(&self) -> Self {
+        match self {
+            RegisterError::Invalid(err) => RegisterError::Invalid(err.clone()),
+            RegisterError::AlreadyRegistered(id) => RegisterError::AlreadyRegistered(*id),
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, T> PartialEq for RegisterError<N, M, T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (RegisterError::Invalid(a), RegisterError::Invalid(b)) => a == b,
+            (RegisterError::AlreadyRegistered(a), RegisterError::AlreadyRegistered(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, T> Eq for RegisterError<N, M, T> {}
+
+impl<const N: usize, const M: usize, T> fmt::Display for RegisterError<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterError::Invalid(err) => fmt::Display::fmt(err, f),
+            RegisterError::AlreadyRegistered(id) => write!(f, "{} is already registered", id),
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, T> fmt::Debug for RegisterError<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RegisterError::Invalid(err) => f.debug_tuple("Invalid").field(err).finish(),
+            RegisterError::AlreadyRegistered(id) => f.debug_tuple("AlreadyRegistered").field(id).finish(),
+        }
+    }
+}
+
+impl<const N: usize, const M: usize, T> std::error::Error for RegisterError<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+}
+
+/// Attaches metadata of type `T` to ids of `StaticIdNxM<N, M>`, see the [module docs](self).
+///
+/// Registered entries are leaked, like interned ids, so handles are `'static`.
+pub struct MetaRegistryNxM<const N: usize, const M: usize, T: 'static> {
+    entries: RwLock<FxHashMap<StaticIdNxM<N, M>, &'static MetaEntryNxM<N, M, T>>>,
+}
+
+impl<const N: usize, const M: usize, T> MetaRegistryNxM<N, M, T> {
+    pub const fn new() -> Self {
+        Self {
+            entries: RwLock::new(HashMap::with_hasher(FxBuildHasher)),
+        }
+    }
+
+    /// Attaches `meta` to `id`. If `id` is already registered, `meta` is
+    /// dropped and the existing handle is returned in `Err`.
+    pub fn register(&self, id: StaticIdNxM<N, M>, meta: T) -> Result<MetaIdNxM<N, M, T>, MetaIdNxM<N, M, T>> {
+        let mut entries = self.entries.write().unwrap();
+        if let Some(&entry) = entries.get(&id) {
+            return Err(MetaIdNxM { entry });
+        }
+        let entry: &'static MetaEntryNxM<N, M, T> = Box::leak(Box::new(MetaEntryNxM { id, meta }));
+        entries.insert(id, entry);
+        Ok(MetaIdNxM { entry })
+    }
+
+    /// The handle of `id`, if it has been registered.
+    #[inline]
+    pub fn get(&self, id: StaticIdNxM<N, M>) -> Option<MetaIdNxM<N, M, T>> {
+        self.entries.read().unwrap().get(&id).map(|&entry| MetaIdNxM { entry })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.read().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().unwrap().is_empty()
+    }
+
+    /// All registered handles, in no particular order.
+    pub fn ids(&self) -> Vec<MetaIdNxM<N, M, T>> {
+        self.entries.read().unwrap().values().map(|&entry| MetaIdNxM { entry }).collect()
+    }
+}
+
+impl<const N: usize, const M: usize, T> MetaRegistryNxM<N, M, T>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    /// Interns `code` and `venue` like `try_from_str` and attaches `meta` to
+    /// the id, see [`Self::register`]. Input rejected by the policies of the
+    /// type is neither interned nor registered.
+    pub fn register_str(&self, code: &str, venue: &str, meta: T) -> Result<MetaIdNxM<N, M, T>, RegisterError<N, M, T>> {
+        let id = StaticIdNxM::try_from_parts(code, venue)?;
+        self.register(id, meta).map_err(RegisterError::AlreadyRegistered)
+    }
+
+    /// The handle of the id for `code` and `venue`, if it has been registered.
+    /// Does not intern anything.
+    pub fn lookup(&self, code: &str, venue: &str) -> Option<MetaIdNxM<N, M, T>> {
+        self.get(StaticIdNxM::lookup(code, venue)?)
+    }
+}
+
+impl<const N: usize, const M: usize, T> Default for MetaRegistryNxM<N, M, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub type MetaRegistry16x0<T> = MetaRegistryNxM<16, 0, T>;
+pub type MetaRegistry16x16<T> = MetaRegistryNxM<16, 16, T>;
+pub type MetaRegistry16x32<T> = MetaRegistryNxM<16, 32, T>;
+pub type MetaRegistry16x64<T> = MetaRegistryNxM<16, 64, T>;
+pub type MetaRegistry32x0<T> = MetaRegistryNxM<32, 0, T>;
+pub type MetaRegistry32x16<T> = MetaRegistryNxM<32, 16, T>;
+pub type MetaRegistry32x32<T> = MetaRegistryNxM<32, 32, T>;
+pub type MetaRegistry32x64<T> = MetaRegistryNxM<32, 64, T>;
+pub type MetaRegistry64x0<T> = MetaRegistryNxM<64, 0, T>;
+pub type MetaRegistry64x16<T> = MetaRegistryNxM<64, 16, T>;
+pub type MetaRegistry64x32<T> = MetaRegistryNxM<64, 32, T>;
+pub type MetaRegistry64x64<T> = MetaRegistryNxM<64, 64, T>;
+pub type MetaRegistry<T> = MetaRegistry32x32<T>;
+
+pub type MetaId16x0<T> = MetaIdNxM<16, 0, T>;
+pub type MetaId16x16<T> = MetaIdNxM<16, 16, T>;
+pub type MetaId16x32<T> = MetaIdNxM<16, 32, T>;
+pub type MetaId16x64<T> = MetaIdNxM<16, 64, T>;
+pub type MetaId32x0<T> = MetaIdNxM<32, 0, T>;
+pub type MetaId32x16<T> = MetaIdNxM<32, 16, T>;
+pub type MetaId32x32<T> = MetaIdNxM<32, 32, T>;
+pub type MetaId32x64<T> = MetaIdNxM<32, 64, T>;
+pub type MetaId64x0<T> = MetaIdNxM<64, 0, T>;
+pub type MetaId64x16<T> = MetaIdNxM<64, 16, T>;
+pub type MetaId64x32<T> = MetaIdNxM<64, 32, T>;
+pub type MetaId64x64<T> = MetaIdNxM<64, 64, T>;
+pub type MetaId<T> = MetaId32x32<T>;
@@ -0,0 +1,129 @@
+use std::borrow::Cow;
+
+/// How code and venue text is normalized before it is interned.
+///
+/// Each `StaticIdNxM` type carries one policy, applied inside `from_str`,
+/// `from_bytes` and `from_combined_str` as well as the lookup and query
+/// methods, so that e.g. `"aapl"`, `" AAPL "` and `"AAPL"` intern to the
+/// same id. Only ASCII is touched: other bytes are kept verbatim.
+///
+/// ```rust
+/// use static_id::Normalization;
+///
+/// let policy = Normalization::CANONICAL;
+/// assert_eq!(policy.apply_str("  brk   b "), "BRK B");
+/// assert_eq!(Normalization::VERBATIM.apply_str(" aapl "), " aapl ");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Normalization {
+    /// Strip leading and trailing ASCII whitespace.
+    pub trim: bool,
+    /// Replace every internal run of ASCII whitespace by a single space.
+    pub collapse_whitespace: bool,
+    /// Convert ASCII lowercase letters to uppercase.
+    pub uppercase: bool,
+}
+
+impl Normalization {
+    /// Keep the bytes as given. This is the default policy.
+    pub const VERBATIM: Self = Self {
+        trim: false,
+        collapse_whitespace: false,
+        uppercase: false,
+    };
+
+    /// Trim, collapse internal whitespace and convert to ASCII uppercase.
+    pub const CANONICAL: Self = Self {
+        trim: true,
+        collapse_whitespace: true,
+        uppercase: true,
+    };
+
+    #[inline]
+    pub fn is_verbatim(&self) -> bool {
+        *self == Self::VERBATIM
+    }
+
+    /// Normalizes `bytes`, borrowing them if they are already normalized.
+    pub fn apply<'a>(&self, bytes: &'a [u8]) -> Cow<'a, [u8]> {
+        let bytes = if self.trim { bytes.trim_ascii() } else { bytes };
+
+        let needs_uppercase = self.uppercase && bytes.iter().any(u8::is_ascii_lowercase);
+        let needs_collapse = self.collapse_whitespace && has_uncollapsed_whitespace(bytes);
+        if !needs_uppercase && !needs_collapse {
+            return Cow::Borrowed(bytes);
+        }
+
+        let mut normalized = Vec::with_capacity(bytes.len());
+        let mut in_whitespace = false;
+        for &b in bytes {
+            if self.collapse_whitespace && b.is_ascii_whitespace() {
+                if !in_whitespace {
+                    normalized.push(b' ');
+                }
+                in_whitespace = true;
+                continue;
+            }
+            in_whitespace = false;
+            normalized.push(if self.uppercase { b.to_ascii_uppercase() } else { b });
+        }
+        Cow::Owned(normalized)
+    }
+
+    /// Normalizes `s`, borrowing it if it is already normalized.
+    pub fn apply_str<'a>(&self, s: &'a str) -> Cow<'a, str> {
+        match self.apply(s.as_bytes()) {
+            // SAFETY: only ASCII bytes are removed or replaced by other ASCII bytes,
+            // so the result is valid UTF-8 whenever the input is.
+            Cow::Borrowed(bytes) => Cow::Borrowed(unsafe { std::str::from_utf8_unchecked(bytes) }),
+            Cow::Owned(bytes) => Cow::Owned(unsafe { String::from_utf8_unchecked(bytes) }),
+        }
+    }
+}
+
+/// Whether `bytes` contain whitespace other than single spaces.
+fn has_uncollapsed_whitespace(bytes: &[u8]) -> bool {
+    let mut previous_whitespace = false;
+    for &b in bytes {
+        let whitespace = b.is_ascii_whitespace();
+        if whitespace && (previous_whitespace || b != b' ') {
+            return true;
+        }
+        previous_whitespace = whitespace;
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbatim_borrows() {
+        let policy = Normalization::VERBATIM;
+        assert!(matches!(policy.apply(b" aapl "), Cow::Borrowed(b" aapl ")));
+    }
+
+    #[test]
+    fn test_canonical() {
+        let policy = Normalization::CANONICAL;
+        assert_eq!(policy.apply_str("aapl"), "AAPL");
+        assert_eq!(policy.apply_str(" AAPL "), "AAPL");
+        assert_eq!(policy.apply_str("brk \t b"), "BRK B");
+        assert_eq!(policy.apply_str("BRK\tB"), "BRK B");
+        assert_eq!(policy.apply_str("삼성 전자"), "삼성 전자");
+        assert!(matches!(policy.apply(b" AAPL "), Cow::Borrowed(b"AAPL")));
+    }
+
+    #[test]
+    fn test_single_rules() {
+        let trim = Normalization { trim: true, ..Normalization::VERBATIM };
+        assert_eq!(trim.apply_str(" a  b "), "a  b");
+
+        let collapse = Normalization { collapse_whitespace: true, ..Normalization::VERBATIM };
+        assert_eq!(collapse.apply_str(" a  b "), " a b ");
+
+        let uppercase = Normalization { uppercase: true, ..Normalization::VERBATIM };
+        assert_eq!(uppercase.apply_str(" a  b "), " A  B ");
+    }
+}
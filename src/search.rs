@@ -0,0 +1,211 @@
+//! The secondary indexes of the intern caches, and the matching and ranking
+//! helpers behind their query methods.
+use crate::interned::Interned;
+use rustc_hash::FxHashMap;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+
+/// The code and venue text of an interned entry.
+pub(crate) trait IdText {
+    fn code_text(&self) -> &str;
+    fn venue_text(&self) -> &str;
+}
+
+/// The entries of an intern cache in intern order, with secondary indexes
+/// from each code and each venue to the entries interned with it.
+///
+/// The indexes are updated on every new intern, so they always cover the
+/// whole universe. The code index is a sorted table, which also serves
+/// prefix and glob queries. Queries take text the cache has already normalized.
+pub(crate) struct IdIndex<E: 'static> {
+    entries: Vec<Interned<E>>,
+    by_code: BTreeMap<&'static str, Vec<Interned<E>>>,
+    by_venue: FxHashMap<&'static str, Vec<Interned<E>>>,
+}
+
+impl<E> Default for IdIndex<E> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            by_code: BTreeMap::new(),
+            by_venue: FxHashMap::default(),
+        }
+    }
+}
+
+impl<E: IdText> IdIndex<E> {
+    /// Adds a newly interned entry, whose intern index is [`Self::len`].
+    pub(crate) fn push(&mut self, id: Interned<E>) {
+        let entry = Interned::as_static(id);
+        self.entries.push(id);
+        self.by_code.entry(entry.code_text()).or_default().push(id);
+        self.by_venue.entry(entry.venue_text()).or_default().push(id);
+    }
+
+    #[inline]
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The entry interned at `index`.
+    #[inline]
+    pub(crate) fn get(&self, index: u32) -> Option<Interned<E>> {
+        self.entries.get(index as usize).copied()
+    }
+
+    /// All entries whose code is `code`, in intern order.
+    #[inline]
+    pub(crate) fn ids_for_code(&self, code: &str) -> &[Interned<E>] {
+        self.by_code.get(code).map_or(&[], Vec::as_slice)
+    }
+
+    /// All entries whose venue is `venue`, in intern order.
+    #[inline]
+    pub(crate) fn ids_for_venue(&self, venue: &str) -> &[Interned<E>] {
+        self.by_venue.get(venue).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterates over the code index entries whose code starts with `prefix`, in code order.
+    fn codes_with_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = (&'static str, &'a Vec<Interned<E>>)> + 'a {
+        self.by_code
+            .range::<str, _>((Bound::Included(prefix), Bound::Unbounded))
+            .take_while(move |(code, _)| code.starts_with(prefix))
+            .map(|(&code, ids)| (code, ids))
+    }
+
+    /// All entries whose code starts with `prefix`, sorted by code.
+    /// If `venue` is given, only entries on that venue are returned.
+    pub(crate) fn search_prefix(&self, prefix: &str, venue: Option<&str>) -> Vec<Interned<E>> {
+        self.codes_with_prefix(prefix)
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .filter(|id| venue.is_none_or(|venue| id.venue_text() == venue))
+            .collect()
+    }
+
+    /// All entries whose code matches the glob `code_pattern`, sorted by code.
+    /// If `venue_pattern` is given, the venue has to match it as well.
+    pub(crate) fn search_glob(&self, code_pattern: &str, venue_pattern: Option<&str>) -> Vec<Interned<E>> {
+        self.codes_with_prefix(literal_prefix(code_pattern))
+            .filter(|(code, _)| glob_match(code_pattern, code))
+            .flat_map(|(_, ids)| ids.iter().copied())
+            .filter(|id| venue_pattern.is_none_or(|pattern| glob_match(pattern, id.venue_text())))
+            .collect()
+    }
+
+    /// The `limit` entries closest to `code` and `venue`, ranked by the sum
+    /// of the edit distances of code and venue, then by code and venue.
+    pub(crate) fn similar(&self, code: &str, venue: &str, limit: usize) -> Vec<(Interned<E>, usize)> {
+        let mut ranked: Vec<_> = self
+            .entries
+            .iter()
+            .map(|&id| (id, edit_distance(code, id.code_text()) + edit_distance(venue, id.venue_text())))
+            .collect();
+        ranked.sort_unstable_by(|(a, da), (b, db)| {
+            da.cmp(db)
+                .then_with(|| a.code_text().cmp(b.code_text()))
+                .then_with(|| a.venue_text().cmp(b.venue_text()))
+        });
+        ranked.truncate(limit);
+        ranked
+    }
+}
+
+/// Returns the literal prefix of a glob pattern, i.e. everything before the
+/// first wildcard.
+#[inline]
+pub(crate) fn literal_prefix(pattern: &str) -> &str {
+    let end = pattern.find(['*', '?']).unwrap_or(pattern.len());
+    &pattern[..end]
+}
+
+/// Matches `text` against a glob `pattern`.
+///
+/// `*` matches any run of characters (including none) and `?` matches exactly
+/// one character. Every other character matches itself.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // position of the last '*' in the pattern and the text position it was tried at
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, tried)) => {
+                    p = star + 1;
+                    t = tried + 1;
+                    backtrack = Some((star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Levenshtein distance between `a` and `b`, counted in characters.
+///
+/// ASCII letters are compared case-insensitively, so `"aapl"` and `"AAPL"`
+/// are at distance 0.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // previous row of the distance matrix
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(!ca.eq_ignore_ascii_case(&cb));
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("ES*", "ESZ4"));
+        assert!(glob_match("ES*", "ES"));
+        assert!(glob_match("E?Z4", "ESZ4"));
+        assert!(glob_match("*Z4", "ESZ4"));
+        assert!(glob_match("*", ""));
+        assert!(glob_match("E*Z*", "ESZ4Z"));
+        assert!(!glob_match("ES?", "ES"));
+        assert!(!glob_match("ES*", "NQZ4"));
+        assert!(!glob_match("ESZ4", "ESZ44"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("AAPL", "AAPL"), 0);
+        assert_eq!(edit_distance("aapl", "AAPL"), 0);
+        assert_eq!(edit_distance("APPL", "AAPL"), 1);
+        assert_eq!(edit_distance("AAP", "AAPL"), 1);
+        assert_eq!(edit_distance("", "KRX"), 3);
+        assert_eq!(edit_distance("NASDQA", "NASDAQ"), 2);
+    }
+
+    #[test]
+    fn test_literal_prefix() {
+        assert_eq!(literal_prefix("ES*"), "ES");
+        assert_eq!(literal_prefix("E?Z"), "E");
+        assert_eq!(literal_prefix("SPY"), "SPY");
+        assert_eq!(literal_prefix("*"), "");
+    }
+}
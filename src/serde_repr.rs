@@ -0,0 +1,321 @@
+//! Serde representations of `StaticIdNxM`.
+//!
+//! Every id type can be written in four representations:
+//!
+//! - [`combined`]: the combined string, `"AAPL@NASDAQ"`
+//! - [`tuple`]: a `(code, venue)` tuple, `["AAPL","NASDAQ"]` in JSON
+//! - [`structure`]: a `{code, venue}` struct, `{"code":"AAPL","venue":"NASDAQ"}` in JSON
+//! - [`index`]: the dense intern index as a `u32`, only meaningful within one process
+//!
+//! Deserialization interns straight from the input: strings and bytes are
+//! borrowed whenever the deserializer can lend them, so no `String` is built
+//! per id. Invalid input is reported as a serde error.
+//!
+//! Each module can be used on a field with `#[serde(with = ...)]`. The plain
+//! `Serialize` and `Deserialize` impls use the representation of the id type,
+//! set with `StaticIdNxM::set_serde_repr`, or else the process-wide default
+//! set with [`set_default_repr`], which starts out as [`SerdeRepr::Combined`].
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//! use static_id::StaticId;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Position {
+//!     #[serde(with = "static_id::serde_repr::tuple")]
+//!     id: StaticId,
+//!     quantity: i64,
+//! }
+//!
+//! let position = Position { id: StaticId::from_str("AAPL", "NASDAQ"), quantity: 100 };
+//! let json = serde_json::to_string(&position).unwrap();
+//! assert_eq!(json, r#"{"id":["AAPL","NASDAQ"],"quantity":100}"#);
+//! ```
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use std::borrow::Cow;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// A serde representation of `StaticIdNxM`, see the [module docs](self).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SerdeRepr {
+    Combined,
+    Tuple,
+    Struct,
+    Index,
+}
+
+impl SerdeRepr {
+    #[inline]
+    const fn to_bits(repr: Option<SerdeRepr>) -> u8 {
+        match repr {
+            None => 0,
+            Some(SerdeRepr::Combined) => 1,
+            Some(SerdeRepr::Tuple) => 2,
+            Some(SerdeRepr::Struct) => 3,
+            Some(SerdeRepr::Index) => 4,
+        }
+    }
+
+    #[inline]
+    fn from_bits(bits: u8) -> Option<SerdeRepr> {
+        match bits {
+            1 => Some(SerdeRepr::Combined),
+            2 => Some(SerdeRepr::Tuple),
+            3 => Some(SerdeRepr::Struct),
+            4 => Some(SerdeRepr::Index),
+            _ => None,
+        }
+    }
+}
+
+static DEFAULT_REPR: AtomicSerdeRepr = AtomicSerdeRepr::new(Some(SerdeRepr::Combined));
+
+/// The process-wide default representation, used by every id type that has none of its own.
+#[inline]
+pub fn default_repr() -> SerdeRepr {
+    DEFAULT_REPR.load().unwrap_or(SerdeRepr::Combined)
+}
+
+/// Sets the process-wide default representation.
+#[inline]
+pub fn set_default_repr(repr: SerdeRepr) {
+    DEFAULT_REPR.store(Some(repr));
+}
+
+/// An optional [`SerdeRepr`] that can be read and replaced without locking.
+///
+/// Every id type keeps its own representation in one of these; `None` defers
+/// to the process-wide default.
+pub struct AtomicSerdeRepr(AtomicU8);
+
+impl AtomicSerdeRepr {
+    pub const fn new(repr: Option<SerdeRepr>) -> Self {
+        Self(AtomicU8::new(SerdeRepr::to_bits(repr)))
+    }
+
+    #[inline]
+    pub fn load(&self) -> Option<SerdeRepr> {
+        SerdeRepr::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    pub fn store(&self, repr: Option<SerdeRepr>) {
+        self.0.store(SerdeRepr::to_bits(repr), Ordering::Relaxed);
+    }
+}
+
+/// A string deserialized without copying whenever the deserializer can lend it.
+/// Accepts strings and UTF-8 bytes.
+pub(crate) struct CowStr<'a>(pub(crate) Cow<'a, str>);
+
+impl<'de: 'a, 'a> Deserialize<'de> for CowStr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(CowStrVisitor(PhantomData))
+    }
+}
+
+struct CowStrVisitor<'a>(PhantomData<&'a str>);
+
+impl<'de: 'a, 'a> Visitor<'de> for CowStrVisitor<'a> {
+    type Value = CowStr<'a>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string")
+    }
+
+    fn visit_borrowed_str<E: de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v.to_string())))
+    }
+
+    fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(CowStr(Cow::Owned(v)))
+    }
+
+    fn visit_borrowed_bytes<E: de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+        let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_borrowed_str(v)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(v)
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        String::from_utf8(v)
+            .map(|v| CowStr(Cow::Owned(v)))
+            .map_err(|err| E::invalid_value(Unexpected::Bytes(err.as_bytes()), &self))
+    }
+}
+
+/// The combined string in the format of the id type, e.g. `"AAPL@NASDAQ"`.
+pub mod combined {
+    use crate::id_cache::HasIdCache;
+    use crate::static_id::StaticIdNxM;
+    use serde::de::{self, Deserializer, Unexpected, Visitor};
+    use serde::Serializer;
+    use std::fmt;
+
+    /// Interns from the transient string, so even owned input is not copied.
+    struct CombinedVisitor<const N: usize, const M: usize>;
+
+    impl<'de, const N: usize, const M: usize> Visitor<'de> for CombinedVisitor<N, M>
+    where
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        type Value = StaticIdNxM<N, M>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a combined id string such as \"AAPL{}NASDAQ\"", StaticIdNxM::<N, M>::format().separator())
+        }
+
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+            StaticIdNxM::try_from_combined_str_with(v, StaticIdNxM::format()).map_err(E::custom)
+        }
+
+        fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+            let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+            self.visit_str(v)
+        }
+    }
+
+    pub fn serialize<S, const N: usize, const M: usize>(id: &StaticIdNxM<N, M>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        serializer.collect_str(id)
+    }
+
+    pub fn deserialize<'de, D, const N: usize, const M: usize>(deserializer: D) -> Result<StaticIdNxM<N, M>, D::Error>
+    where
+        D: Deserializer<'de>,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        deserializer.deserialize_str(CombinedVisitor)
+    }
+}
+
+/// A `(code, venue)` tuple.
+pub mod tuple {
+    use super::CowStr;
+    use crate::id_cache::HasIdCache;
+    use crate::static_id::StaticIdNxM;
+    use serde::de::{self, Deserializer, SeqAccess, Visitor};
+    use serde::{Serialize, Serializer};
+    use std::fmt;
+
+    struct TupleVisitor<const N: usize, const M: usize>;
+
+    impl<'de, const N: usize, const M: usize> Visitor<'de> for TupleVisitor<N, M>
+    where
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        type Value = StaticIdNxM<N, M>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "a (code, venue) tuple")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let CowStr(code) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let CowStr(venue) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            StaticIdNxM::try_from_parts(&code, &venue).map_err(de::Error::custom)
+        }
+    }
+
+    pub fn serialize<S, const N: usize, const M: usize>(id: &StaticIdNxM<N, M>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        (id.code_str(), id.venue_str()).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize, const M: usize>(deserializer: D) -> Result<StaticIdNxM<N, M>, D::Error>
+    where
+        D: Deserializer<'de>,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        deserializer.deserialize_tuple(2, TupleVisitor)
+    }
+}
+
+/// A `{code, venue}` struct.
+pub mod structure {
+    use super::CowStr;
+    use crate::id_cache::HasIdCache;
+    use crate::static_id::StaticIdNxM;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    #[serde(rename = "StaticId")]
+    pub(crate) struct PartsRef<'a> {
+        pub(crate) code: &'a str,
+        pub(crate) venue: &'a str,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "StaticId")]
+    pub(crate) struct Parts<'a> {
+        #[serde(borrow)]
+        pub(crate) code: CowStr<'a>,
+        #[serde(borrow)]
+        pub(crate) venue: CowStr<'a>,
+    }
+
+    pub fn serialize<S, const N: usize, const M: usize>(id: &StaticIdNxM<N, M>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        PartsRef { code: id.code_str(), venue: id.venue_str() }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D, const N: usize, const M: usize>(deserializer: D) -> Result<StaticIdNxM<N, M>, D::Error>
+    where
+        D: Deserializer<'de>,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        let Parts { code: CowStr(code), venue: CowStr(venue) } = Parts::deserialize(deserializer)?;
+        StaticIdNxM::try_from_parts(&code, &venue).map_err(serde::de::Error::custom)
+    }
+}
+
+/// The dense intern index as a `u32`.
+///
+/// Indices depend on the order ids were interned in, so this representation
+/// is only suitable for data that stays within one process, or whose reader
+/// interns the same ids in the same order first.
+pub mod index {
+    use crate::id_cache::HasIdCache;
+    use crate::static_id::StaticIdNxM;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S, const N: usize, const M: usize>(id: &StaticIdNxM<N, M>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        serializer.serialize_u32(id.index())
+    }
+
+    pub fn deserialize<'de, D, const N: usize, const M: usize>(deserializer: D) -> Result<StaticIdNxM<N, M>, D::Error>
+    where
+        D: Deserializer<'de>,
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        let index = u32::deserialize(deserializer)?;
+        StaticIdNxM::from_index(index)
+            .ok_or_else(|| serde::de::Error::custom(format!("no id is interned at index {}", index)))
+    }
+}
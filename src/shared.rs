@@ -0,0 +1,326 @@
+//! An interner shared by the processes of one host through a memory-mapped file.
+//!
+//! The intern cache of a `StaticIdNxM` type assigns indices in the order ids
+//! are first seen, so two processes give different indices to the same id.
+//! A [`SharedInterner`] keeps the table of interned cores in a file, typically
+//! under `/dev/shm`, so every process attached to the file gets the same index
+//! for the same code and venue.
+//!
+//! Inserts take a lock on the file, so they are serialized across processes.
+//! Lookups take no lock: an entry is published with a release store after its
+//! core is written, and read with an acquire load.
+//!
+//! # File layout
+//! All integers are native-endian; the file is only shared within one host.
+//!
+//! | offset            | size              | content                                      |
+//! |-------------------|-------------------|----------------------------------------------|
+//! | 0                 | 8                 | magic `b"SIDSHM01"`                          |
+//! | 8                 | 4                 | `N`                                          |
+//! | 12                | 4                 | `M`                                          |
+//! | 16                | 4                 | capacity: maximum number of ids              |
+//! | 20                | 4                 | number of index slots, a power of two        |
+//! | 24                | 4                 | number of ids interned so far                |
+//! | 64                | capacity × (N+M)  | table of cores, see [`IdCoreNxM`]            |
+//! | 64 + table, to 8  | slots × 4         | open-addressing index: table position + 1, 0 if empty |
+//!
+//! The slot of a core is found by linear probing from its FNV-1a 64 hash.
+use crate::fingerprint::fnv1a;
+use crate::id_cache::HasIdCache;
+use crate::static_id::{IdCoreNxM, StaticIdNxM};
+use memmap2::MmapRaw;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+
+const MAGIC: &[u8; 8] = b"SIDSHM01";
+const HEADER_SIZE: usize = 64;
+
+#[repr(C)]
+struct Header {
+    magic: [u8; 8],
+    code_len: u32,
+    venue_len: u32,
+    capacity: u32,
+    slots: u32,
+    len: AtomicU32,
+}
+
+/// A fixed-capacity intern table in a shared memory file, see the [module docs](self).
+///
+/// ```rust
+/// use static_id::shared::SharedInterner;
+/// use static_id::StaticId;
+///
+/// let path = std::env::temp_dir().join(format!("static-id-doc-{}", std::process::id()));
+/// let interner = SharedInterner::<32, 32>::open(&path, 1024).unwrap();
+/// // another process attaching to the same path sees the same indices
+/// let other = SharedInterner::<32, 32>::open(&path, 1024).unwrap();
+///
+/// let id = StaticId::from_str("AAPL", "NASDAQ");
+/// let index = interner.intern_id(id).unwrap();
+/// assert_eq!(other.get_id(id), Some(index));
+/// assert_eq!(other.resolve(index), Some(id));
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct SharedInterner<const N: usize, const M: usize> {
+    file: File,
+    map: MmapRaw,
+    capacity: u32,
+    slots: u32,
+    /// the file lock is held per open file, so threads of this process
+    /// also serialize their inserts here
+    insert_lock: Mutex<()>,
+}
+
+impl<const N: usize, const M: usize> SharedInterner<N, M> {
+    /// Attaches to the interner in the file at `path`, creating it with room
+    /// for `capacity` ids if the file does not exist or is empty.
+    ///
+    /// An existing file keeps its own capacity; it is rejected if it was
+    /// created for another `N` or `M`.
+    pub fn open(path: impl AsRef<Path>, capacity: u32) -> io::Result<Self> {
+        let file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path)?;
+        file.lock()?;
+        let result = Self::init(&file, capacity);
+        file.unlock()?;
+        let (capacity, slots) = result?;
+
+        let map = MmapRaw::map_raw(&file)?;
+        if map.len() < Self::file_size(capacity, slots) {
+            return Err(invalid_data("shared interner file is truncated"));
+        }
+        Ok(Self {
+            file,
+            map,
+            capacity,
+            slots,
+            insert_lock: Mutex::new(()),
+        })
+    }
+
+    /// Writes the header of an empty file, or checks the header of an existing one.
+    /// Returns the capacity and number of slots. The caller holds the file lock.
+    fn init(file: &File, capacity: u32) -> io::Result<(u32, u32)> {
+        if file.metadata()?.len() == 0 {
+            let slots = capacity
+                .checked_mul(2)
+                .and_then(u32::checked_next_power_of_two)
+                .ok_or_else(|| invalid_input("shared interner capacity is too large"))?;
+            file.set_len(Self::file_size(capacity, slots) as u64)?;
+            let header = Header {
+                magic: *MAGIC,
+                code_len: N as u32,
+                venue_len: M as u32,
+                capacity,
+                slots,
+                len: AtomicU32::new(0),
+            };
+            let map = MmapRaw::map_raw(file)?;
+            // Safety: the file is at least `HEADER_SIZE` long and page aligned
+            unsafe { (map.as_mut_ptr() as *mut Header).write(header) };
+            map.flush()?;
+            return Ok((capacity, slots));
+        }
+
+        if (file.metadata()?.len() as usize) < HEADER_SIZE {
+            return Err(invalid_data("shared interner file is truncated"));
+        }
+        let map = MmapRaw::map_raw(file)?;
+        // Safety: as above, and every bit pattern is a valid `Header`
+        let header = unsafe { &*(map.as_ptr() as *const Header) };
+        if &header.magic != MAGIC {
+            return Err(invalid_data("not a shared interner file: bad magic"));
+        }
+        if header.code_len as usize != N || header.venue_len as usize != M {
+            return Err(invalid_data(format!(
+                "shared interner file holds {}x{} ids, not {}x{}",
+                header.code_len, header.venue_len, N, M
+            )));
+        }
+        if !header.slots.is_power_of_two() || header.slots / 2 < header.capacity {
+            return Err(invalid_data("shared interner file has an invalid index size"));
+        }
+        Ok((header.capacity, header.slots))
+    }
+
+    fn table_offset() -> usize {
+        HEADER_SIZE
+    }
+
+    fn slots_offset(capacity: u32) -> usize {
+        (HEADER_SIZE + capacity as usize * IdCoreNxM::<N, M>::SIZE).next_multiple_of(8)
+    }
+
+    fn file_size(capacity: u32, slots: u32) -> usize {
+        Self::slots_offset(capacity) + slots as usize * 4
+    }
+
+    fn header(&self) -> &Header {
+        // Safety: checked by `open`
+        unsafe { &*(self.map.as_ptr() as *const Header) }
+    }
+
+    fn slot(&self, slot: u32) -> &AtomicU32 {
+        debug_assert!(slot < self.slots);
+        // Safety: in bounds and 4-byte aligned, checked by `open`
+        unsafe { &*(self.map.as_ptr().add(Self::slots_offset(self.capacity)) as *const AtomicU32).add(slot as usize) }
+    }
+
+    fn raw_core(&self, index: u32) -> &[u8] {
+        debug_assert!(index < self.capacity);
+        let size = IdCoreNxM::<N, M>::SIZE;
+        // Safety: in bounds, checked by `open`. Entries are never written after
+        // they are published.
+        unsafe { std::slice::from_raw_parts(self.map.as_ptr().add(Self::table_offset() + index as usize * size), size) }
+    }
+
+    /// Looks `raw` up in the index.
+    fn probe(&self, raw: &[u8]) -> Probe {
+        let mask = self.slots - 1;
+        let mut slot = fnv1a(raw) as u32 & mask;
+        // the index is never more than half full, unless another process corrupted it
+        for _ in 0..self.slots {
+            let entry = self.slot(slot).load(Ordering::Acquire);
+            if entry == 0 {
+                return Probe::Vacant(slot);
+            }
+            let index = entry - 1;
+            if index < self.capacity && self.raw_core(index) == raw {
+                return Probe::Found(index);
+            }
+            slot = (slot + 1) & mask;
+        }
+        Probe::Corrupt
+    }
+
+    /// Maximum number of ids the file can hold.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.capacity
+    }
+
+    /// Number of ids interned so far, by every attached process.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.header().len.load(Ordering::Acquire) as usize
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The shared index of `core`, if it has been interned. Takes no lock.
+    pub fn get(&self, core: &IdCoreNxM<N, M>) -> Option<u32> {
+        match self.probe(core.as_bytes()) {
+            Probe::Found(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// The shared index of `core`, interning it if needed.
+    ///
+    /// Fails with `ErrorKind::StorageFull` when the file holds `capacity` ids.
+    pub fn intern(&self, core: &IdCoreNxM<N, M>) -> io::Result<u32> {
+        let raw = core.as_bytes();
+        if let Probe::Found(index) = self.probe(raw) {
+            return Ok(index);
+        }
+
+        let _guard = self.insert_lock.lock().unwrap();
+        self.file.lock()?;
+        let result = self.insert(raw);
+        self.file.unlock()?;
+        result
+    }
+
+    /// Inserts `raw` unless another process did first. The caller holds both locks.
+    fn insert(&self, raw: &[u8]) -> io::Result<u32> {
+        let slot = match self.probe(raw) {
+            Probe::Found(index) => return Ok(index),
+            Probe::Vacant(slot) => slot,
+            Probe::Corrupt => return Err(invalid_data("shared interner index is corrupt")),
+        };
+        let header = self.header();
+        let index = header.len.load(Ordering::Acquire);
+        if index >= self.capacity {
+            return Err(io::Error::new(io::ErrorKind::StorageFull, "shared interner is full"));
+        }
+        // Safety: in bounds, and not visible to readers before the release stores below
+        unsafe {
+            let dst = self.map.as_mut_ptr().add(Self::table_offset() + index as usize * raw.len());
+            std::ptr::copy_nonoverlapping(raw.as_ptr(), dst, raw.len());
+        }
+        self.slot(slot).store(index + 1, Ordering::Release);
+        header.len.store(index + 1, Ordering::Release);
+        Ok(index)
+    }
+
+    /// The core at shared `index`, or `None` if nothing is interned there yet
+    /// or another process wrote an invalid core. Takes no lock.
+    pub fn core(&self, index: u32) -> Option<&IdCoreNxM<N, M>> {
+        if index as usize >= self.len() {
+            return None;
+        }
+        IdCoreNxM::try_ref_from_bytes(self.raw_core(index)).ok()
+    }
+
+    /// The shared index of `id`, if it has been interned. Takes no lock.
+    #[inline]
+    pub fn get_id(&self, id: StaticIdNxM<N, M>) -> Option<u32> {
+        self.get(id.id_ptr.core())
+    }
+
+    /// The shared index of `id`, interning it if needed.
+    #[inline]
+    pub fn intern_id(&self, id: StaticIdNxM<N, M>) -> io::Result<u32> {
+        self.intern(id.id_ptr.core())
+    }
+
+    /// The id at shared `index`, interned into the cache of this process
+    /// under its policies, as by `StaticIdNxM::try_from_str`. `None` if
+    /// nothing is interned there yet or the policies of this process reject
+    /// the core, e.g. one written by a process with laxer validation.
+    pub fn resolve(&self, index: u32) -> Option<StaticIdNxM<N, M>>
+    where
+        StaticIdNxM<N, M>: HasIdCache<N, M>,
+    {
+        let core = self.core(index)?;
+        let mut cache = StaticIdNxM::<N, M>::lock_id_cache();
+        let id_ptr = cache.try_intern_bytes(core.code.as_str().as_bytes(), core.venue.as_str().as_bytes()).ok()?;
+        Some(StaticIdNxM { id_ptr })
+    }
+}
+
+enum Probe {
+    Found(u32),
+    Vacant(u32),
+    Corrupt,
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn invalid_input<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_fits() {
+        assert!(std::mem::size_of::<Header>() <= HEADER_SIZE);
+    }
+}
@@ -1,52 +1,325 @@
+use crate::error::{IdError, IdPart};
+use crate::fingerprint::{fingerprint128, fingerprint64};
+use crate::format::{AtomicIdFormat, DisplayWith, IdFormat};
+use crate::id_cache::{HasIdCache, IdCacheNxM};
+use crate::id_table::IdTableNxM;
+use crate::interned::Interned;
+use crate::search::IdText;
+use crate::serde_repr::{self, AtomicSerdeRepr, SerdeRepr};
 use crate::symbol::Symbol;
 //use crate::IdCore;
 use once_cell::sync::Lazy;
-use rustc_hash::FxHashMap;
+use rustc_hash::FxHasher;
 use std::{
+    cmp::Ordering,
     hash::Hash, 
     hash::Hasher,
-    ptr::eq as ptr_eq
+    ops::Deref,
 };
 
 use std::sync::Mutex;
 use serde::{Serialize, Deserialize};
 use serde::{Serializer, Deserializer};
 
-#[derive(PartialEq, Eq, Hash, Clone, Serialize, Deserialize, Debug, Default)]
+/// The code and venue of an id.
+///
+/// # Layout
+/// `IdCoreNxM<N, M>` is `#[repr(C)]`: the `N` bytes of the code followed by
+/// the `M` bytes of the venue, `N + M` bytes in total with alignment 1 and
+/// no padding. Each part uses the layout of [`Symbol`]. In C:
+///
+/// ```c
+/// struct IdCore32x32 {
+///     unsigned char code[32];  // UTF-8, padded with 0xFF
+///     unsigned char venue[32]; // UTF-8, padded with 0xFF
+/// };
+/// ```
+///
+/// This layout is stable, so cores can be passed through shared memory with
+/// [`IdCoreNxM::as_bytes`] and [`IdCoreNxM::try_ref_from_bytes`].
+///
+/// Cores are ordered by code, then by venue.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize, Debug, Default)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
 pub struct IdCoreNxM<const N: usize, const M: usize> {
     pub code: Symbol<N>,
     pub venue: Symbol<M>,
 }
 
+impl<const N: usize, const M: usize> IdCoreNxM<N, M> {
+    /// Size in bytes of the raw core.
+    pub const SIZE: usize = N + M;
+
+    /// The raw bytes of the core, in the layout documented on [`IdCoreNxM`].
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        Self::slice_as_bytes(std::slice::from_ref(self))
+    }
+
+    /// The raw bytes of consecutive cores.
+    #[inline]
+    pub fn slice_as_bytes(cores: &[Self]) -> &[u8] {
+        // Safety: `Self` is `#[repr(C)]` over byte arrays, without padding
+        unsafe { std::slice::from_raw_parts(cores.as_ptr() as *const u8, std::mem::size_of_val(cores)) }
+    }
+
+    /// Views `Self::SIZE` raw bytes as a core, checking that code and venue
+    /// are UTF-8 padded with `0xFF`.
+    ///
+    /// ```rust
+    /// use static_id::{IdCore16x16, StaticId16x16};
+    ///
+    /// let id = StaticId16x16::from_str("ESZ4", "CME");
+    /// let bytes = id.get_id().as_bytes().to_vec();
+    /// assert_eq!(bytes.len(), IdCore16x16::SIZE);
+    ///
+    /// let core = IdCore16x16::try_ref_from_bytes(&bytes).unwrap();
+    /// assert_eq!(core, id.get_id());
+    /// ```
+    #[inline]
+    pub fn try_ref_from_bytes(bytes: &[u8]) -> Result<&Self, IdError> {
+        if bytes.len() != Self::SIZE {
+            return Err(IdError::SizeMismatch { expected: Self::SIZE, found: bytes.len() });
+        }
+        Ok(&Self::try_slice_from_bytes(bytes)?[0])
+    }
+
+    /// Views raw bytes as consecutive cores, e.g. a table in a mapped region.
+    /// The length of `bytes` must be a multiple of `Self::SIZE`.
+    pub fn try_slice_from_bytes(bytes: &[u8]) -> Result<&[Self], IdError> {
+        if !bytes.len().is_multiple_of(Self::SIZE) {
+            return Err(IdError::SliceSizeMismatch { core_size: Self::SIZE, found: bytes.len() });
+        }
+        for raw in bytes.chunks_exact(Self::SIZE) {
+            Symbol::<N>::check_raw(&raw[..N], IdPart::Code)?;
+            Symbol::<M>::check_raw(&raw[N..], IdPart::Venue)?;
+        }
+        // Safety: `Self` is `#[repr(C)]` over byte arrays with alignment 1,
+        // and every core has just been checked to be valid
+        Ok(unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const Self, bytes.len() / Self::SIZE) })
+    }
+}
+
+/// The interned allocation a `StaticIdNxM` points to: the core plus the
+/// data the intern cache attaches to it.
+///
+/// Dereferences to the core, so `id.id_ptr.code` reads the code. Entries
+/// compare by core and hash as their precomputed hash, which is how the
+/// intern cache's [`crate::interned::InternSet`] finds them.
+#[derive(Debug)]
+pub struct IdEntryNxM<const N: usize, const M: usize> {
+    core: IdCoreNxM<N, M>,
+    index: u32,
+    hash: u64,
+    fingerprint: u64,
+    fingerprint128: u128,
+}
+
+impl<const N: usize, const M: usize> IdEntryNxM<N, M> {
+    /// `hash` must be `hash_parts` of the code and venue of `core`.
+    #[inline]
+    pub(crate) fn new(core: IdCoreNxM<N, M>, index: u32, hash: u64) -> Self {
+        debug_assert_eq!(hash, hash_parts(core.code.as_str().as_bytes(), core.venue.as_str().as_bytes()));
+        let (code, venue) = (core.code.as_str(), core.venue.as_str());
+        Self {
+            hash,
+            fingerprint: fingerprint64(code, venue),
+            fingerprint128: fingerprint128(code, venue),
+            core,
+            index,
+        }
+    }
+
+    #[inline]
+    pub fn core(&self) -> &IdCoreNxM<N, M> {
+        &self.core
+    }
+
+    /// Position of the entry in intern order: the first id interned in a
+    /// type's cache has index 0, the next one 1 and so on. Indices are dense
+    /// but only meaningful within one process.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// The hash of the code and venue, computed once at intern time. It keys
+    /// the intern cache and is what `StaticIdNxM` feeds to a `Hasher`. Unlike
+    /// [`Self::fingerprint`], its algorithm is not part of the stable API.
+    #[inline]
+    pub fn precomputed_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// The 64-bit content fingerprint, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The 128-bit content fingerprint, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint128(&self) -> u128 {
+        self.fingerprint128
+    }
+}
+
+/// The hash of an id with the stored `code` and `venue` bytes.
+#[inline]
+pub(crate) fn hash_parts(code: &[u8], venue: &[u8]) -> u64 {
+    let mut hasher = FxHasher::default();
+    hasher.write(code);
+    hasher.write_u8(0xFF);
+    hasher.write(venue);
+    hasher.finish()
+}
+
+impl<const N: usize, const M: usize> PartialEq for IdEntryNxM<N, M> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.core == other.core
+    }
+}
+
+impl<const N: usize, const M: usize> Eq for IdEntryNxM<N, M> {}
+
+impl<const N: usize, const M: usize> Hash for IdEntryNxM<N, M> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl<const N: usize, const M: usize> IdText for IdEntryNxM<N, M> {
+    #[inline]
+    fn code_text(&self) -> &str {
+        self.core.code.as_str()
+    }
+
+    #[inline]
+    fn venue_text(&self) -> &str {
+        self.core.venue.as_str()
+    }
+}
+
+impl<const N: usize, const M: usize> Deref for IdEntryNxM<N, M> {
+    type Target = IdCoreNxM<N, M>;
+
+    #[inline]
+    fn deref(&self) -> &IdCoreNxM<N, M> {
+        &self.core
+    }
+}
+
+/// An interned id: an [`Interned`] handle to the entry of its code and venue.
+///
+/// Ids are only created through the global cache of their type, so that the
+/// index of an id is the one [`StaticIdNxM::from_index`] resolves.
 #[derive(Clone, Copy)]
 pub struct StaticIdNxM<const N: usize, const M: usize> {
-    pub id_ptr: &'static IdCoreNxM<N, M>,
+    pub(crate) id_ptr: Interned<IdEntryNxM<N, M>>,
 }
 
 
-impl<const N: usize, const M: usize> std::fmt::Display for StaticIdNxM<N, M> {
+impl<const N: usize, const M: usize> std::fmt::Display for StaticIdNxM<N, M>
+where
+    Self: HasIdCache<N, M>,
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}@{}", self.id_ptr.code, self.id_ptr.venue)
+        write!(f, "{}", self.display_with(Self::id_format().load()))
     }
 }
 
-impl<const N: usize, const M: usize> std::fmt::Debug for StaticIdNxM<N, M> {
+impl<const N: usize, const M: usize> std::fmt::Debug for StaticIdNxM<N, M>
+where
+    Self: HasIdCache<N, M>,
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}@{}", self.id_ptr.code, self.id_ptr.venue)
+        write!(f, "{}", self.display_with(Self::id_format().load()))
     }
 }
 
 impl<const N: usize, const M: usize> PartialEq for StaticIdNxM<N, M> {
     fn eq(&self, other: &Self) -> bool {
-        ptr_eq(self.id_ptr, other.id_ptr)
+        self.id_ptr == other.id_ptr
     }
 }
 
 impl<const N: usize, const M: usize> Eq for StaticIdNxM<N, M> {}
 
+/// Writes the hash precomputed at intern time, 8 bytes whatever `N` and `M`.
 impl<const N: usize, const M: usize> Hash for StaticIdNxM<N, M> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.id_ptr.hash(state);
+        state.write_u64(self.id_ptr.hash);
+    }
+}
+
+/// Orders ids lexicographically by code, then by venue, consistently with
+/// `Eq`. See [`ByIndex`] for the cheaper order of interning.
+impl<const N: usize, const M: usize> Ord for StaticIdNxM<N, M> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self == other {
+            return Ordering::Equal;
+        }
+        self.id_ptr.core().cmp(other.id_ptr.core())
+    }
+}
+
+impl<const N: usize, const M: usize> PartialOrd for StaticIdNxM<N, M> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// An id ordered by its intern index instead of its text.
+///
+/// Comparing two indices is a single integer comparison, and the order is
+/// deterministic as long as ids are interned in a deterministic order. It
+/// is unrelated to the lexicographic order and only meaningful within one
+/// process.
+///
+/// ```rust
+/// use static_id::{ByIndex, StaticId};
+/// use std::collections::BTreeSet;
+///
+/// let msft = StaticId::from_str("MSFT", "NASDAQ");
+/// let aapl = StaticId::from_str("AAPL", "NASDAQ");
+/// let by_index: BTreeSet<_> = [aapl, msft].into_iter().map(ByIndex).collect();
+/// assert!(by_index.iter().map(|id| id.0).eq([msft, aapl]));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ByIndex<const N: usize, const M: usize>(pub StaticIdNxM<N, M>);
+
+impl<const N: usize, const M: usize> Ord for ByIndex<N, M> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.index().cmp(&other.0.index())
+    }
+}
+
+impl<const N: usize, const M: usize> PartialOrd for ByIndex<N, M> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize, const M: usize> std::fmt::Debug for ByIndex<N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize, const M: usize> From<StaticIdNxM<N, M>> for ByIndex<N, M> {
+    #[inline]
+    fn from(id: StaticIdNxM<N, M>) -> Self {
+        Self(id)
     }
 }
 
@@ -67,6 +340,31 @@ impl<const N: usize, const M: usize> StaticIdNxM<N, M> {
         self.id_ptr.code.upper_bound() + self.id_ptr.venue.upper_bound()    
     }
 
+    /// The interned entry of the id.
+    #[inline]
+    pub fn entry(&self) -> &'static IdEntryNxM<N, M> {
+        Interned::as_static(self.id_ptr)
+    }
+
+    /// The dense intern index of the id, see [`IdEntryNxM::index`].
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.id_ptr.index
+    }
+
+    /// The 64-bit content fingerprint of the id, stable across processes and
+    /// machines, see [`crate::fingerprint`]. Computed once at intern time.
+    #[inline]
+    pub fn fingerprint(&self) -> u64 {
+        self.id_ptr.fingerprint
+    }
+
+    /// The 128-bit content fingerprint of the id, see [`crate::fingerprint`].
+    #[inline]
+    pub fn fingerprint128(&self) -> u128 {
+        self.id_ptr.fingerprint128
+    }
+
     #[inline]
     #[must_use]
     pub fn code_str(&self) -> &str {
@@ -78,14 +376,45 @@ impl<const N: usize, const M: usize> StaticIdNxM<N, M> {
     pub fn venue_str(&self) -> &str {
         self.id_ptr.venue.as_str()
     }
+
+    /// Displays the id in `format` instead of the format of its type.
+    #[inline]
+    pub fn display_with(&self, format: IdFormat) -> DisplayWith<'_, N, M> {
+        DisplayWith { id: self, format }
+    }
 }
 
-impl<const N: usize, const M: usize> Serialize for StaticIdNxM<N, M> {
+impl<const N: usize, const M: usize> Serialize for StaticIdNxM<N, M>
+where
+    Self: HasIdCache<N, M>,
+{
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        serializer.serialize_str(&self.to_string())
+        match Self::serde_repr() {
+            SerdeRepr::Combined => serde_repr::combined::serialize(self, serializer),
+            SerdeRepr::Tuple => serde_repr::tuple::serialize(self, serializer),
+            SerdeRepr::Struct => serde_repr::structure::serialize(self, serializer),
+            SerdeRepr::Index => serde_repr::index::serialize(self, serializer),
+        }
+    }
+}
+
+impl<'de, const N: usize, const M: usize> Deserialize<'de> for StaticIdNxM<N, M>
+where
+    Self: HasIdCache<N, M>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match Self::serde_repr() {
+            SerdeRepr::Combined => serde_repr::combined::deserialize(deserializer),
+            SerdeRepr::Tuple => serde_repr::tuple::deserialize(deserializer),
+            SerdeRepr::Struct => serde_repr::structure::deserialize(deserializer),
+            SerdeRepr::Index => serde_repr::index::deserialize(deserializer),
+        }
     }
 }
 
@@ -117,76 +446,114 @@ pub type StaticId64x32 = StaticIdNxM<64, 32>;
 pub type StaticId64x64 = StaticIdNxM<64, 64>;
 pub type StaticId = StaticIdNxM<32, 32>;
 
-static ID_CACHE_16X0: Lazy<Mutex<FxHashMap<IdCore16x0, &'static IdCore16x0>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_16X0: IdTableNxM<16, 0> = IdTableNxM::new();
+static ID_CACHE_16X0: Lazy<Mutex<IdCacheNxM<16, 0>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_16X0)));
+static ID_FORMAT_16X0: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_16X0: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_16X0: Lazy<StaticId16x0> = Lazy::new(|| StaticId16x0::from_str(""));
 
-static ID_CACHE_16X16: Lazy<Mutex<FxHashMap<IdCore16x16, &'static IdCore16x16>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_16X16: IdTableNxM<16, 16> = IdTableNxM::new();
+static ID_CACHE_16X16: Lazy<Mutex<IdCacheNxM<16, 16>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_16X16)));
+static ID_FORMAT_16X16: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_16X16: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_16X16: Lazy<StaticId16x16> = Lazy::new(|| StaticId16x16::from_str("", ""));
 
-static ID_CACHE_16X32: Lazy<Mutex<FxHashMap<IdCore16x32, &'static IdCore16x32>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_16X32: IdTableNxM<16, 32> = IdTableNxM::new();
+static ID_CACHE_16X32: Lazy<Mutex<IdCacheNxM<16, 32>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_16X32)));
+static ID_FORMAT_16X32: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_16X32: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_16X32: Lazy<StaticId16x32> = Lazy::new(|| StaticId16x32::from_str("", ""));
 
-static ID_CACHE_16X64: Lazy<Mutex<FxHashMap<IdCore16x64, &'static IdCore16x64>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_16X64: IdTableNxM<16, 64> = IdTableNxM::new();
+static ID_CACHE_16X64: Lazy<Mutex<IdCacheNxM<16, 64>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_16X64)));
+static ID_FORMAT_16X64: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_16X64: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_16X64: Lazy<StaticId16x64> = Lazy::new(|| StaticId16x64::from_str("", ""));
 
-static ID_CACHE_32X0: Lazy<Mutex<FxHashMap<IdCore32x0, &'static IdCore32x0>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_32X0: IdTableNxM<32, 0> = IdTableNxM::new();
+static ID_CACHE_32X0: Lazy<Mutex<IdCacheNxM<32, 0>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_32X0)));
+static ID_FORMAT_32X0: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_32X0: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_32X0: Lazy<StaticId32x0> = Lazy::new(|| StaticId32x0::from_str(""));
 
-static ID_CACHE_32X16: Lazy<Mutex<FxHashMap<IdCore32x16, &'static IdCore32x16>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_32X16: IdTableNxM<32, 16> = IdTableNxM::new();
+static ID_CACHE_32X16: Lazy<Mutex<IdCacheNxM<32, 16>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_32X16)));
+static ID_FORMAT_32X16: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_32X16: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_32X16: Lazy<StaticId32x16> = Lazy::new(|| StaticId32x16::from_str("", ""));
 
-static ID_CACHE_32X32: Lazy<Mutex<FxHashMap<IdCore32x32, &'static IdCore32x32>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_32X32: IdTableNxM<32, 32> = IdTableNxM::new();
+static ID_CACHE_32X32: Lazy<Mutex<IdCacheNxM<32, 32>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_32X32)));
+static ID_FORMAT_32X32: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_32X32: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_32X32: Lazy<StaticId32x32> = Lazy::new(|| StaticId32x32::from_str("", ""));
 
-static ID_CACHE_32X64: Lazy<Mutex<FxHashMap<IdCore32x64, &'static IdCore32x64>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_32X64: IdTableNxM<32, 64> = IdTableNxM::new();
+static ID_CACHE_32X64: Lazy<Mutex<IdCacheNxM<32, 64>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_32X64)));
+static ID_FORMAT_32X64: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_32X64: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_32X64: Lazy<StaticId32x64> = Lazy::new(|| StaticId32x64::from_str("", ""));
 
-static ID_CACHE_64X0: Lazy<Mutex<FxHashMap<IdCore64x0, &'static IdCore64x0>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_64X0: IdTableNxM<64, 0> = IdTableNxM::new();
+static ID_CACHE_64X0: Lazy<Mutex<IdCacheNxM<64, 0>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_64X0)));
+static ID_FORMAT_64X0: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_64X0: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_64X0: Lazy<StaticId64x0> = Lazy::new(|| StaticId64x0::from_str(""));
 
-static ID_CACHE_64X16: Lazy<Mutex<FxHashMap<IdCore64x16, &'static IdCore64x16>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_64X16: IdTableNxM<64, 16> = IdTableNxM::new();
+static ID_CACHE_64X16: Lazy<Mutex<IdCacheNxM<64, 16>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_64X16)));
+static ID_FORMAT_64X16: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_64X16: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_64X16: Lazy<StaticId64x16> = Lazy::new(|| StaticId64x16::from_str("", ""));
 
-static ID_CACHE_64X32: Lazy<Mutex<FxHashMap<IdCore64x32, &'static IdCore64x32>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_64X32: IdTableNxM<64, 32> = IdTableNxM::new();
+static ID_CACHE_64X32: Lazy<Mutex<IdCacheNxM<64, 32>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_64X32)));
+static ID_FORMAT_64X32: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_64X32: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_64X32: Lazy<StaticId64x32> = Lazy::new(|| StaticId64x32::from_str("", ""));
 
-static ID_CACHE_64X64: Lazy<Mutex<FxHashMap<IdCore64x64, &'static IdCore64x64>>> = Lazy::new(|| Mutex::new(FxHashMap::default()));
+static ID_TABLE_64X64: IdTableNxM<64, 64> = IdTableNxM::new();
+static ID_CACHE_64X64: Lazy<Mutex<IdCacheNxM<64, 64>>> = Lazy::new(|| Mutex::new(IdCacheNxM::with_table(&ID_TABLE_64X64)));
+static ID_FORMAT_64X64: AtomicIdFormat = AtomicIdFormat::new(IdFormat::DEFAULT);
+static ID_SERDE_REPR_64X64: AtomicSerdeRepr = AtomicSerdeRepr::new(None);
 static DEFAULT_ID_64X64: Lazy<StaticId64x64> = Lazy::new(|| StaticId64x64::from_str("", ""));
 
 impl StaticId16x0 {
     #[inline]
     #[must_use]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(code: &str) -> Self {
-        let id = IdCore16x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-
-        };
-        let mut cache = ID_CACHE_16X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), b"");
         StaticId16x0 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8]) -> Self {
-        let id = IdCore16x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-        };
-        let mut cache = ID_CACHE_16X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, b"");
         StaticId16x0 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), b"")?;
+        Ok(StaticId16x0 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, b"")?;
+        Ok(StaticId16x0 { id_ptr: interned })
+    }
+
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_16X0.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore16x0 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -196,40 +563,64 @@ impl Default for StaticId16x0 {
     }
 }
 
+impl HasIdCache<16, 0> for StaticId16x0 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<16, 0>> {
+        &ID_CACHE_16X0
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_16X0
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_16X0
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<16, 0> {
+        &ID_TABLE_16X0
+    }
+}
+
 impl StaticId32x0 {
     #[inline]
     #[must_use]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(code: &str) -> Self {
-        let id = IdCore32x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-        };
-
-        let mut cache = ID_CACHE_32X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), b"");
         StaticId32x0 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8]) -> Self {
-        let id = IdCore32x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-        };
-        let mut cache = ID_CACHE_32X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, b"");
         StaticId32x0 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), b"")?;
+        Ok(StaticId32x0 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, b"")?;
+        Ok(StaticId32x0 { id_ptr: interned })
+    }
+
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_32X0.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore32x0 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -239,39 +630,64 @@ impl Default for StaticId32x0 {
     }
 }
 
+impl HasIdCache<32, 0> for StaticId32x0 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<32, 0>> {
+        &ID_CACHE_32X0
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_32X0
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_32X0
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<32, 0> {
+        &ID_TABLE_32X0
+    }
+}
+
 impl StaticId64x0 {
     #[inline]
     #[must_use]
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(code: &str) -> Self {
-        let id = IdCore64x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-        };
-        let mut cache = ID_CACHE_64X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), b"");
         StaticId64x0 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8]) -> Self {
-        let id = IdCore64x0 {
-            code: Symbol::from(code),
-            venue: Symbol::from(""),
-        };
-        let mut cache = ID_CACHE_64X0.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, b"");
         StaticId64x0 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), b"")?;
+        Ok(StaticId64x0 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, b"")?;
+        Ok(StaticId64x0 { id_ptr: interned })
+    }
+
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_64X0.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore64x0 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -281,47 +697,74 @@ impl Default for StaticId64x0 {
     }
 }
 
+impl HasIdCache<64, 0> for StaticId64x0 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<64, 0>> {
+        &ID_CACHE_64X0
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_64X0
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_64X0
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<64, 0> {
+        &ID_TABLE_64X0
+    }
+}
+
 impl StaticId16x16 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore16x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId16x16 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore16x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId16x16 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId16x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId16x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_16X16.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore16x16 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -331,13 +774,25 @@ impl Default for StaticId16x16 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId16x16 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId16x16, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId16x16::from_combined_str(&s))
+impl HasIdCache<16, 16> for StaticId16x16 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<16, 16>> {
+        &ID_CACHE_16X16
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_16X16
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_16X16
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<16, 16> {
+        &ID_TABLE_16X16
     }
 }
 
@@ -347,43 +802,48 @@ impl StaticId16x32 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore16x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId16x32 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore16x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId16x32 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId16x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId16x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_16X32.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore16x32 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -393,13 +853,25 @@ impl Default for StaticId16x32 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId16x32 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId16x32, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId16x32::from_combined_str(&s))
+impl HasIdCache<16, 32> for StaticId16x32 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<16, 32>> {
+        &ID_CACHE_16X32
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_16X32
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_16X32
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<16, 32> {
+        &ID_TABLE_16X32
     }
 }
 // 16x64
@@ -407,43 +879,48 @@ impl StaticId16x64 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore16x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId16x64 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore16x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_16X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId16x64 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId16x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId16x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_16X64.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore16x64 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -453,13 +930,25 @@ impl Default for StaticId16x64 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId16x64 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId16x64, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId16x64::from_combined_str(&s))
+impl HasIdCache<16, 64> for StaticId16x64 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<16, 64>> {
+        &ID_CACHE_16X64
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_16X64
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_16X64
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<16, 64> {
+        &ID_TABLE_16X64
     }
 }
 
@@ -468,43 +957,48 @@ impl StaticId32x16 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore32x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId32x16 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore32x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId32x16 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId32x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId32x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_32X16.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore32x16 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -514,13 +1008,25 @@ impl Default for StaticId32x16 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId32x16 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId32x16, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId32x16::from_combined_str(&s))
+impl HasIdCache<32, 16> for StaticId32x16 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<32, 16>> {
+        &ID_CACHE_32X16
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_32X16
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_32X16
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<32, 16> {
+        &ID_TABLE_32X16
     }
 }
 
@@ -530,43 +1036,48 @@ impl StaticId32x32 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore32x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId32x32 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore32x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId32x32 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId32x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId32x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_32X32.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore32x32 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -576,13 +1087,25 @@ impl Default for StaticId32x32 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId32x32 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId32x32, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId32x32::from_combined_str(&s))
+impl HasIdCache<32, 32> for StaticId32x32 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<32, 32>> {
+        &ID_CACHE_32X32
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_32X32
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_32X32
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<32, 32> {
+        &ID_TABLE_32X32
     }
 }
 
@@ -592,43 +1115,48 @@ impl StaticId32x64 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore32x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId32x64 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore32x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_32X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId32x64 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId32x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId32x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_32X64.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore32x64 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -638,13 +1166,25 @@ impl Default for StaticId32x64 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId32x64 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId32x64, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId32x64::from_combined_str(&s))
+impl HasIdCache<32, 64> for StaticId32x64 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<32, 64>> {
+        &ID_CACHE_32X64
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_32X64
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_32X64
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<32, 64> {
+        &ID_TABLE_32X64
     }
 }
 
@@ -654,43 +1194,48 @@ impl StaticId64x16 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore64x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId64x16 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore64x16 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X16.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId64x16 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId64x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId64x16 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_64X16.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore64x16 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -700,13 +1245,25 @@ impl Default for StaticId64x16 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId64x16 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId64x16, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId64x16::from_combined_str(&s))
+impl HasIdCache<64, 16> for StaticId64x16 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<64, 16>> {
+        &ID_CACHE_64X16
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_64X16
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_64X16
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<64, 16> {
+        &ID_TABLE_64X16
     }
 }
 
@@ -716,43 +1273,48 @@ impl StaticId64x32 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore64x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId64x32 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore64x32 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X32.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId64x32 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId64x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId64x32 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_64X32.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore64x32 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -762,13 +1324,25 @@ impl Default for StaticId64x32 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId64x32 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId64x32, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId64x32::from_combined_str(&s))
+impl HasIdCache<64, 32> for StaticId64x32 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<64, 32>> {
+        &ID_CACHE_64X32
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_64X32
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_64X32
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<64, 32> {
+        &ID_TABLE_64X32
     }
 }
 
@@ -778,43 +1352,48 @@ impl StaticId64x64 {
     #[inline]
     #[must_use]
     pub fn from_str(code: &str, venue: &str) -> Self {
-        let id = IdCore64x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code.as_bytes(), venue.as_bytes());
         StaticId64x64 { id_ptr: interned }
     }
 
     #[inline]
     #[must_use]
     pub fn from_bytes(code: &[u8], venue: &[u8]) -> Self {
-        let id = IdCore64x64 {
-            code: Symbol::from(code),
-            venue: Symbol::from(venue),
-        };
-        let mut cache = ID_CACHE_64X64.lock().unwrap();
-        let interned = cache.entry(id.clone()).or_insert_with(|| Box::leak(Box::new(id)));
+        let interned = Self::lock_id_cache().intern_bytes(code, venue);
         StaticId64x64 { id_ptr: interned }
     }
 
+    #[inline]
+    pub fn try_from_str(code: &str, venue: &str) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code.as_bytes(), venue.as_bytes())?;
+        Ok(StaticId64x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_bytes(code: &[u8], venue: &[u8]) -> Result<Self, IdError> {
+        let interned = Self::lock_id_cache().try_intern_bytes(code, venue)?;
+        Ok(StaticId64x64 { id_ptr: interned })
+    }
+
+    #[inline]
+    pub fn try_from_combined_str(combined: &str) -> Result<Self, IdError> {
+        Self::try_from_combined_str_with(combined, Self::format())
+    }
+
     #[inline]
     #[must_use]
     pub fn from_combined_str(combined: &str) -> Self {
-        let (code, venue) = combined.split_at(combined.find('@').unwrap());
-        let venue = &venue[1..];
-        Self::from_str(code, venue)
+        Self::from_combined_str_with(combined, Self::format())
     }
 
     #[inline]
     pub fn cache_len() -> usize {
-        ID_CACHE_64X64.lock().unwrap().len()
+        Self::lock_id_cache().len()
     }
 
     #[inline]
     pub fn get_id(&self) -> &IdCore64x64 {
-        self.id_ptr
+        &self.id_ptr
     }
 }
 
@@ -824,12 +1403,24 @@ impl Default for StaticId64x64 {
     }
 }
 
-impl<'de> Deserialize<'de> for StaticId64x64 {
-    fn deserialize<D>(deserializer: D) -> Result<StaticId64x64, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let s = String::deserialize(deserializer)?;
-        Ok(StaticId64x64::from_combined_str(&s))
+impl HasIdCache<64, 64> for StaticId64x64 {
+    #[inline]
+    fn id_cache() -> &'static Mutex<IdCacheNxM<64, 64>> {
+        &ID_CACHE_64X64
+    }
+
+    #[inline]
+    fn id_format() -> &'static AtomicIdFormat {
+        &ID_FORMAT_64X64
+    }
+
+    #[inline]
+    fn id_serde_repr() -> &'static AtomicSerdeRepr {
+        &ID_SERDE_REPR_64X64
+    }
+
+    #[inline]
+    fn id_table() -> &'static IdTableNxM<64, 64> {
+        &ID_TABLE_64X64
     }
 }
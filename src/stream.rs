@@ -0,0 +1,269 @@
+//! Dictionary-encoded binary streams of ids.
+//!
+//! Sequences of ids, such as the instruments of a tick capture, usually repeat
+//! a small set of ids many times. [`IdEncoder`] writes the text of each id
+//! only the first time it appears and a varint reference afterwards;
+//! [`IdDecoder`] reads the stream back and re-interns every id into the cache
+//! of the process.
+//!
+//! The stream starts with the 4-byte magic `b"SID1"`, followed by records.
+//! Every record starts with an unsigned LEB128 varint tag:
+//!
+//! - `0` defines the next dictionary entry: the varint length and bytes of the
+//!   code, then the varint length and bytes of the venue. The id is emitted.
+//! - `k > 0` emits dictionary entry `k - 1`.
+//!
+//! ```rust
+//! use static_id::stream::{IdDecoder, IdEncoder};
+//! use static_id::StaticId;
+//!
+//! let aapl = StaticId::from_str("AAPL", "NASDAQ");
+//! let msft = StaticId::from_str("MSFT", "NASDAQ");
+//!
+//! let mut encoder = IdEncoder::new(Vec::new()).unwrap();
+//! for id in [aapl, msft, aapl, aapl] {
+//!     encoder.encode(id).unwrap();
+//! }
+//! let bytes = encoder.finish().unwrap();
+//!
+//! let decoder = IdDecoder::<_, 32, 32>::new(bytes.as_slice()).unwrap();
+//! let ids: Vec<StaticId> = decoder.collect::<Result<_, _>>().unwrap();
+//! assert_eq!(ids, vec![aapl, msft, aapl, aapl]);
+//! ```
+use crate::id_cache::HasIdCache;
+use crate::static_id::StaticIdNxM;
+use rustc_hash::FxHashMap;
+use std::io::{self, Read, Write};
+
+const MAGIC: &[u8; 4] = b"SID1";
+
+/// Longest code or venue accepted by the decoder, to bound the memory a
+/// corrupt stream can make it allocate.
+const MAX_PART_LEN: u64 = 1 << 16;
+
+/// Writes a dictionary-encoded stream of ids, see the [module docs](self).
+pub struct IdEncoder<W: Write, const N: usize, const M: usize> {
+    writer: W,
+    /// intern index of each id written so far => its dictionary entry
+    dictionary: FxHashMap<u32, u64>,
+}
+
+impl<W: Write, const N: usize, const M: usize> IdEncoder<W, N, M> {
+    /// Starts a stream by writing its header to `writer`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        Ok(Self {
+            writer,
+            dictionary: FxHashMap::default(),
+        })
+    }
+
+    /// Appends `id` to the stream.
+    pub fn encode(&mut self, id: StaticIdNxM<N, M>) -> io::Result<()> {
+        if let Some(&entry) = self.dictionary.get(&id.index()) {
+            return write_varint(&mut self.writer, entry + 1);
+        }
+        write_varint(&mut self.writer, 0)?;
+        write_part(&mut self.writer, id.code_str())?;
+        write_part(&mut self.writer, id.venue_str())?;
+        let entry = self.dictionary.len() as u64;
+        self.dictionary.insert(id.index(), entry);
+        Ok(())
+    }
+
+    /// Number of distinct ids written so far.
+    #[inline]
+    pub fn dictionary_len(&self) -> usize {
+        self.dictionary.len()
+    }
+
+    /// Flushes the writer and returns it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// Reads a dictionary-encoded stream of ids, see the [module docs](self).
+///
+/// Every decoded id is interned into the cache of `StaticIdNxM<N, M>`, under
+/// the normalization and validation policies of the type. The decoder is an
+/// iterator over the ids of the stream.
+///
+/// The decoder does not buffer: it reads varints a byte at a time, so a file
+/// or socket should be wrapped in a [`BufReader`](std::io::BufReader).
+pub struct IdDecoder<R: Read, const N: usize, const M: usize> {
+    reader: R,
+    dictionary: Vec<StaticIdNxM<N, M>>,
+    code: Vec<u8>,
+    venue: Vec<u8>,
+}
+
+impl<R: Read, const N: usize, const M: usize> IdDecoder<R, N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    /// Reads and checks the header of the stream.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not an id stream: bad magic"));
+        }
+        Ok(Self {
+            reader,
+            dictionary: Vec::new(),
+            code: Vec::new(),
+            venue: Vec::new(),
+        })
+    }
+
+    /// Reads the next id, or `None` at the end of the stream.
+    pub fn decode(&mut self) -> io::Result<Option<StaticIdNxM<N, M>>> {
+        let tag = match read_varint(&mut self.reader, true)? {
+            Some(tag) => tag,
+            None => return Ok(None),
+        };
+        if tag > 0 {
+            return match self.dictionary.get((tag - 1) as usize) {
+                Some(&id) => Ok(Some(id)),
+                None => Err(invalid_data(format!("reference to undefined dictionary entry {}", tag - 1))),
+            };
+        }
+        read_part(&mut self.reader, &mut self.code)?;
+        read_part(&mut self.reader, &mut self.venue)?;
+        let id_ptr = StaticIdNxM::<N, M>::lock_id_cache()
+            .try_intern_bytes(&self.code, &self.venue)
+            .map_err(invalid_data)?;
+        let id = StaticIdNxM { id_ptr };
+        self.dictionary.push(id);
+        Ok(Some(id))
+    }
+
+    /// Number of distinct ids read so far.
+    #[inline]
+    pub fn dictionary_len(&self) -> usize {
+        self.dictionary.len()
+    }
+}
+
+impl<R: Read, const N: usize, const M: usize> Iterator for IdDecoder<R, N, M>
+where
+    StaticIdNxM<N, M>: HasIdCache<N, M>,
+{
+    type Item = io::Result<StaticIdNxM<N, M>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.decode().transpose()
+    }
+}
+
+fn invalid_data<E>(error: E) -> io::Error
+where
+    E: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut buf = [0u8; 10];
+    let mut len = 0;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf[len] = byte;
+            len += 1;
+            break;
+        }
+        buf[len] = byte | 0x80;
+        len += 1;
+    }
+    writer.write_all(&buf[..len])
+}
+
+/// Reads a varint. At a clean end of input returns `None` if `eof_ok`.
+fn read_varint<R: Read>(reader: &mut R, eof_ok: bool) -> io::Result<Option<u64>> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8];
+        match reader.read_exact(&mut byte) {
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof && eof_ok && shift == 0 => return Ok(None),
+            result => result?,
+        }
+        // the tenth byte holds the 64th bit only
+        if shift == 63 && byte[0] > 1 {
+            return Err(invalid_data("varint is longer than 64 bits"));
+        }
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(Some(value));
+        }
+    }
+    Err(invalid_data("varint is longer than 64 bits"))
+}
+
+fn write_part<W: Write>(writer: &mut W, part: &str) -> io::Result<()> {
+    write_varint(writer, part.len() as u64)?;
+    writer.write_all(part.as_bytes())
+}
+
+fn read_part<R: Read>(reader: &mut R, buf: &mut Vec<u8>) -> io::Result<()> {
+    let len = read_varint(reader, false)?.unwrap_or_default();
+    if len > MAX_PART_LEN {
+        return Err(invalid_data(format!("code or venue of {} bytes", len)));
+    }
+    buf.resize(len as usize, 0);
+    reader.read_exact(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_varint() {
+        for value in [0, 1, 127, 128, 300, u32::MAX as u64, u64::MAX] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, value).unwrap();
+            assert_eq!(read_varint(&mut buf.as_slice(), false).unwrap(), Some(value));
+        }
+        let mut buf = Vec::new();
+        write_varint(&mut buf, 300).unwrap();
+        assert_eq!(buf, [0xac, 0x02]);
+
+        assert_eq!(read_varint(&mut [].as_slice(), true).unwrap(), None);
+        assert!(read_varint(&mut [0x80].as_slice(), true).is_err());
+        assert!(read_varint(&mut [0xff; 11].as_slice(), true).is_err());
+
+        let mut overflow = [0xff; 10];
+        overflow[9] = 0x02;
+        let err = read_varint(&mut overflow.as_slice(), false).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        overflow[9] = 0x01;
+        assert_eq!(read_varint(&mut overflow.as_slice(), false).unwrap(), Some(u64::MAX));
+    }
+
+    /// Returns `Interrupted` before every byte.
+    struct Interrupting<'a>(&'a [u8], bool);
+
+    impl Read for Interrupting<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.1 = !self.1;
+            if self.1 {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(self.0.len()).min(1);
+            buf[..len].copy_from_slice(&self.0[..len]);
+            self.0 = &self.0[len..];
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_varint_retries_interrupted_reads() {
+        let mut reader = Interrupting(&[0xac, 0x02], false);
+        assert_eq!(read_varint(&mut reader, true).unwrap(), Some(300));
+        assert_eq!(read_varint(&mut reader, true).unwrap(), None);
+    }
+}
@@ -1,10 +1,34 @@
+use crate::error::{IdError, IdPart};
+use serde::de::{self, Unexpected, Visitor};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::ptr::copy_nonoverlapping;
 use std::str::from_utf8_unchecked;
 
 const END_MARK: u8 = 255;
 
+/// The largest `len` or smaller at which `bytes` can be cut without splitting
+/// a UTF-8 character. Invalid UTF-8 is treated as single bytes.
+#[inline]
+pub(crate) fn floor_char_boundary(bytes: &[u8], mut len: usize) -> usize {
+    while len > 0 && len < bytes.len() && (bytes[len] & 0xC0) == 0x80 {
+        len -= 1;
+    }
+    len
+}
+
+/// A string of at most `N` bytes stored inline.
+///
+/// # Layout
+/// `Symbol<N>` is `#[repr(C)]`: exactly `N` bytes with alignment 1 and no
+/// padding, `unsigned char[N]` in C. The string is stored from byte 0 and
+/// the rest is filled with the terminator `0xFF`, a byte that never occurs
+/// in UTF-8. The length is the position of the first `0xFF`, or `N` if there
+/// is none. This layout is stable and may be shared with other processes,
+/// see [`Symbol::as_raw_bytes`] and [`Symbol::try_ref_from_raw_bytes`].
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+#[repr(C)]
 pub struct Symbol<const N: usize> {
     symbol: [u8; N],
 }
@@ -15,6 +39,22 @@ impl<const N: usize> std::fmt::Debug for Symbol<N> {
     }
 }
 
+/// Orders symbols lexicographically by their string, like `str`. The
+/// `0xFF` padding does not take part, so `"AB"` sorts before `"ABC"`.
+impl<const N: usize> Ord for Symbol<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl<const N: usize> PartialOrd for Symbol<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<const N: usize> Default for Symbol<N> {
     fn default() -> Self {
         Self { symbol: [END_MARK; N] }
@@ -30,29 +70,47 @@ impl<const N: usize> Serialize for Symbol<N> {
     }
 }
 
+struct SymbolVisitor<const N: usize>;
+
+impl<const N: usize> Visitor<'_> for SymbolVisitor<N> {
+    type Value = Symbol<N>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string of at most {} bytes", N)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() > N {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(Symbol::from(v))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let v = std::str::from_utf8(v).map_err(|_| E::invalid_value(Unexpected::Bytes(v), &self))?;
+        self.visit_str(v)
+    }
+}
+
 impl<'de, const N: usize> Deserialize<'de> for Symbol<N> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        let s: String = String::deserialize(deserializer)?;
-        if s.len() > N {
-            return Err(serde::de::Error::custom(format!(
-                "String is too long. Expected at most {} characters, found {}",
-                N, s.len()
-            )));
-        }
-        Ok(Self::from(s.as_str()))
+        deserializer.deserialize_str(SymbolVisitor)
     }
 }
 
+/// Keeps the prefix of `slice` that fits in `N` bytes, see the layout on
+/// [`Symbol`]. Nothing after a `0xFF` byte is kept, so symbols equal as
+/// strings are equal as arrays.
 impl<const N: usize> From<&[u8]> for Symbol<N> {
     #[inline]
     fn from(slice: &[u8]) -> Self {
         let mut symbol = [END_MARK; N];
-        let len = slice.len().min(N);
+        let stored = Self::stored_prefix(slice);
         unsafe {
-            copy_nonoverlapping(slice.as_ptr(), symbol.as_mut_ptr(), len);
+            copy_nonoverlapping(stored.as_ptr(), symbol.as_mut_ptr(), stored.len());
         }
         Self { symbol }
     }
@@ -90,13 +148,66 @@ impl<const N: usize> Symbol<N> {
     #[inline]
     #[must_use]
     pub fn as_str(&self) -> &str {
-        let len = self.symbol.iter().position(|&c| c == END_MARK).unwrap_or(32);
+        let len = self.len();
         unsafe { from_utf8_unchecked(&self.symbol[..len]) }
     }
 
+    /// The raw bytes of the symbol, in the layout documented on [`Symbol`].
+    #[inline]
+    pub fn as_raw_bytes(&self) -> &[u8; N] {
+        &self.symbol
+    }
+
+    /// Views raw bytes in the layout documented on [`Symbol`] as a symbol.
+    /// Returns an error naming `part`, the part of an id the bytes hold, if
+    /// the string is not UTF-8 or is not padded with `0xFF`.
+    #[inline]
+    pub fn try_ref_from_raw_bytes(raw: &[u8; N], part: IdPart) -> Result<&Self, IdError> {
+        Self::check_raw(raw, part)?;
+        // Safety: `Symbol<N>` is a `#[repr(C)]` wrapper of `[u8; N]`
+        Ok(unsafe { &*(raw as *const [u8; N] as *const Self) })
+    }
+
+    /// The prefix of `bytes` a symbol built from them would hold: at most `N`
+    /// bytes, ending before any `END_MARK` and at a character boundary.
+    #[inline]
+    pub(crate) fn stored_prefix(bytes: &[u8]) -> &[u8] {
+        let len = bytes.iter().take(N).position(|&c| c == END_MARK).unwrap_or(bytes.len().min(N));
+        &bytes[..floor_char_boundary(bytes, len)]
+    }
+
+    /// Checks that `raw` holds a UTF-8 string padded with `END_MARK`.
+    pub(crate) fn check_raw(raw: &[u8], part: IdPart) -> Result<(), IdError> {
+        let len = raw.iter().position(|&c| c == END_MARK).unwrap_or(raw.len());
+        if raw[len..].iter().any(|&c| c != END_MARK) {
+            return Err(IdError::InvalidPadding { part });
+        }
+        std::str::from_utf8(&raw[..len]).map_err(|_| IdError::InvalidUtf8 { part })?;
+        Ok(())
+    }
+
     /// # Safety
     /// The caller must ensure that the slice has at most `N` elements.
     pub unsafe fn copy_from_slice(&mut self, slice: &[u8], length: usize) {
         copy_nonoverlapping(slice.as_ptr(), self.symbol.as_mut_ptr(), length);
     }
-}
\ No newline at end of file
+}
+/// The archived form of a `Symbol`: the same `N` bytes, padded with `END_MARK`.
+#[cfg(feature = "rkyv")]
+impl<const N: usize> ArchivedSymbol<N> {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.symbol.iter().position(|&c| c == END_MARK).unwrap_or(N)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The bytes of the symbol. Archives are not trusted to hold UTF-8.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.symbol[..self.len()]
+    }
+}
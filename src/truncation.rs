@@ -0,0 +1,138 @@
+use crate::error::{IdError, IdPart};
+use crate::fingerprint::fnv1a;
+use crate::symbol::{floor_char_boundary, Symbol};
+use std::borrow::Cow;
+
+/// What happens to a code or venue longer than the `N` or `M` bytes of its type.
+///
+/// Each `StaticIdNxM` type carries one policy, applied after normalization
+/// wherever text is interned or looked up.
+///
+/// - [`Truncation::Truncate`] (the default) keeps the first `N` bytes, cut
+///   back to a character boundary, so two inputs sharing them become the same id.
+/// - [`Truncation::Reject`] refuses the input: the fallible constructors
+///   return [`IdError::TooLong`] and the infallible ones panic.
+/// - [`Truncation::HashSuffix`] keeps a prefix cut at a character boundary
+///   and appends `'~'` and 6 hex digits of a hash of the whole input, so
+///   that inputs sharing a prefix stay distinct (up to a 1 in 2^24 chance).
+///
+/// ```rust
+/// use static_id::{StaticId16x16, Truncation, Overflow};
+///
+/// StaticId16x16::set_truncation(Truncation::HashSuffix);
+/// let (id, report) = StaticId16x16::try_from_parts_reported("SPXW241220P05000000", "CBOE").unwrap();
+/// assert_eq!(report.code, Overflow::HashSuffixed);
+/// assert_eq!(report.venue, Overflow::Fits);
+/// assert_eq!(id.code_str().len(), 16);
+/// assert!(id.code_str().starts_with("SPXW24122~"));
+/// assert_ne!(id, StaticId16x16::from_str("SPXW241220P05100000", "CBOE"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Truncation {
+    Reject,
+    #[default]
+    Truncate,
+    HashSuffix,
+}
+
+/// What a [`Truncation`] policy did to one code or venue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// The input fit and is stored as given.
+    #[default]
+    Fits,
+    /// The input was cut to the bound.
+    Truncated,
+    /// The input was cut and a hash of it appended.
+    HashSuffixed,
+}
+
+/// What happened to the code and the venue of an interned id.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TruncationReport {
+    pub code: Overflow,
+    pub venue: Overflow,
+}
+
+impl TruncationReport {
+    /// Whether both code and venue are stored as given.
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        self.code == Overflow::Fits && self.venue == Overflow::Fits
+    }
+}
+
+/// Number of hex digits of the hash suffix.
+const SUFFIX_DIGITS: usize = 6;
+
+impl Truncation {
+    /// The bytes a `Symbol<N>` stores for `bytes` under this policy.
+    pub(crate) fn fit<const N: usize>(self, bytes: &[u8], part: IdPart) -> Result<(Cow<'_, [u8]>, Overflow), IdError> {
+        let stored = Symbol::<N>::stored_prefix(bytes);
+        if stored.len() == bytes.len() {
+            return Ok((Cow::Borrowed(stored), Overflow::Fits));
+        }
+        match self {
+            Truncation::Truncate => Ok((Cow::Borrowed(stored), Overflow::Truncated)),
+            Truncation::Reject => Err(IdError::TooLong {
+                part,
+                value: String::from_utf8_lossy(bytes).into_owned(),
+                max_len: N,
+            }),
+            Truncation::HashSuffix => Ok((Cow::Owned(hash_suffixed(bytes, N)), Overflow::HashSuffixed)),
+        }
+    }
+}
+
+/// A prefix of `bytes` followed by `'~'` and a hash of `bytes`, `bound` bytes long.
+fn hash_suffixed(bytes: &[u8], bound: usize) -> Vec<u8> {
+    let suffix = format!("~{:0width$x}", fnv1a(bytes) & ((1 << (4 * SUFFIX_DIGITS)) - 1), width = SUFFIX_DIGITS);
+    if bound <= suffix.len() {
+        // no room for a prefix: keep as many hash digits as fit
+        return suffix.as_bytes()[1..].iter().copied().take(bound).collect();
+    }
+    // the prefix ends before any terminator byte and at a character boundary,
+    // treating invalid UTF-8 as single bytes
+    let terminator = bytes.iter().position(|&b| b == 0xFF).unwrap_or(bytes.len());
+    let len = floor_char_boundary(bytes, terminator.min(bound - suffix.len()));
+    let mut fitted = bytes[..len].to_vec();
+    fitted.extend_from_slice(suffix.as_bytes());
+    fitted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit() {
+        let fit = |policy: Truncation, bytes: &'static [u8]| policy.fit::<8>(bytes, IdPart::Code);
+
+        assert_eq!(fit(Truncation::Reject, b"ESZ4").unwrap(), (Cow::Borrowed(&b"ESZ4"[..]), Overflow::Fits));
+        assert_eq!(fit(Truncation::Truncate, b"ABCDEFGHIJ").unwrap().1, Overflow::Truncated);
+        assert!(matches!(fit(Truncation::Reject, b"ABCDEFGHIJ"), Err(IdError::TooLong { max_len: 8, .. })));
+
+        // "가" is three bytes: 8 bytes would split the third one
+        let (cut, overflow) = fit(Truncation::Truncate, "가가가".as_bytes()).unwrap();
+        assert_eq!(overflow, Overflow::Truncated);
+        assert_eq!(&*cut, "가가".as_bytes());
+
+        let (a, overflow) = fit(Truncation::HashSuffix, b"ABCDEFGHIJ").unwrap();
+        let (b, _) = fit(Truncation::HashSuffix, b"ABCDEFGHIK").unwrap();
+        assert_eq!(overflow, Overflow::HashSuffixed);
+        assert_eq!(a.len(), 8);
+        assert!(a.starts_with(b"A~"));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_hash_suffix_edges() {
+        // "é" is two bytes: the prefix must not split it
+        let fitted = hash_suffixed("aéééé".as_bytes(), 9);
+        assert!(std::str::from_utf8(&fitted).is_ok());
+        assert_eq!(&fitted[..1], b"a");
+        assert_eq!(hash_suffixed(b"ABCDEFGHIJ", 4).len(), 4);
+        assert!(hash_suffixed(b"ABCDEFGHIJ", 0).is_empty());
+        assert!(hash_suffixed(b"A\xFFB", 16).starts_with(b"A~"));
+    }
+}
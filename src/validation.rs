@@ -0,0 +1,186 @@
+use crate::error::{IdError, IdPart};
+
+/// Character classes accepted by a [`Validation`] policy.
+///
+/// Every character belongs to exactly one class: non-ASCII characters are
+/// `non_ascii`, ASCII control characters (including tab and newline) are
+/// `control`, and the printable ASCII characters are split into `space`,
+/// `alphanumeric` and `punctuation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CharClasses {
+    pub alphanumeric: bool,
+    pub punctuation: bool,
+    pub space: bool,
+    pub control: bool,
+    pub non_ascii: bool,
+}
+
+impl CharClasses {
+    /// Every character.
+    pub const ANY: Self = Self {
+        alphanumeric: true,
+        punctuation: true,
+        space: true,
+        control: true,
+        non_ascii: true,
+    };
+
+    /// Printable ASCII: letters, digits, punctuation and the space character.
+    pub const PRINTABLE_ASCII: Self = Self {
+        alphanumeric: true,
+        punctuation: true,
+        space: true,
+        control: false,
+        non_ascii: false,
+    };
+
+    /// ASCII letters and digits only.
+    pub const ALPHANUMERIC: Self = Self {
+        alphanumeric: true,
+        punctuation: false,
+        space: false,
+        control: false,
+        non_ascii: false,
+    };
+
+    #[inline]
+    pub fn contains(&self, ch: char) -> bool {
+        if !ch.is_ascii() {
+            self.non_ascii
+        } else if ch.is_ascii_control() {
+            self.control
+        } else if ch == ' ' {
+            self.space
+        } else if ch.is_ascii_alphanumeric() {
+            self.alphanumeric
+        } else {
+            self.punctuation
+        }
+    }
+}
+
+impl Default for CharClasses {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+/// A custom validation rule: returns the reason when it rejects a value.
+pub type Predicate = fn(&str) -> Result<(), String>;
+
+/// Validation rules for the code or the venue of an id type.
+///
+/// The rules are checked by the fallible constructors (`try_from_str`,
+/// `try_from_bytes`, `try_from_combined_str`) and by `Deserialize`, after
+/// the normalization policy has been applied. The infallible constructors
+/// do not validate.
+///
+/// ```rust
+/// use static_id::{CharClasses, IdPart, Validation};
+///
+/// let code = Validation {
+///     allowed: CharClasses::PRINTABLE_ASCII,
+///     min_len: 1,
+///     custom: Some(|code| match code.contains('@') {
+///         true => Err("'@' is reserved as the separator".to_string()),
+///         false => Ok(()),
+///     }),
+/// };
+/// assert!(code.check(IdPart::Code, "AAPL").is_ok());
+/// assert!(code.check(IdPart::Code, "AA@PL").is_err());
+/// assert!(code.check(IdPart::Code, "").is_err());
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Validation {
+    /// Character classes every character has to belong to.
+    pub allowed: CharClasses,
+    /// Minimum length in bytes.
+    pub min_len: usize,
+    /// Custom predicate, returning the reason of a rejection.
+    pub custom: Option<Predicate>,
+}
+
+impl Validation {
+    /// Accepts everything. This is the default policy.
+    pub const ANY: Self = Self {
+        allowed: CharClasses::ANY,
+        min_len: 0,
+        custom: None,
+    };
+
+    /// Checks `value` as the `part` of an id.
+    pub fn check(&self, part: IdPart, value: &str) -> Result<(), IdError> {
+        if value.len() < self.min_len {
+            return Err(IdError::TooShort {
+                part,
+                value: value.to_string(),
+                min_len: self.min_len,
+            });
+        }
+        if let Some((position, ch)) = value.char_indices().find(|&(_, ch)| !self.allowed.contains(ch)) {
+            return Err(IdError::DisallowedChar {
+                part,
+                value: value.to_string(),
+                ch,
+                position,
+            });
+        }
+        if let Some(custom) = self.custom {
+            custom(value).map_err(|reason| IdError::Rejected {
+                part,
+                value: value.to_string(),
+                reason,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for Validation {
+    fn default() -> Self {
+        Self::ANY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_char_classes() {
+        let printable = CharClasses::PRINTABLE_ASCII;
+        assert!(printable.contains('A'));
+        assert!(printable.contains('@'));
+        assert!(printable.contains(' '));
+        assert!(!printable.contains('\t'));
+        assert!(!printable.contains('\u{0}'));
+        assert!(!printable.contains('é'));
+
+        assert!(CharClasses::ANY.contains('\t'));
+        assert!(!CharClasses::ALPHANUMERIC.contains('-'));
+    }
+
+    #[test]
+    fn test_validation_errors() {
+        let validation = Validation {
+            allowed: CharClasses::ALPHANUMERIC,
+            min_len: 2,
+            custom: Some(|s| match s.starts_with('X') {
+                true => Err("test codes are not allowed".to_string()),
+                false => Ok(()),
+            }),
+        };
+
+        assert_eq!(validation.check(IdPart::Venue, "KRX"), Ok(()));
+        assert!(matches!(
+            validation.check(IdPart::Venue, "K"),
+            Err(IdError::TooShort { min_len: 2, .. })
+        ));
+        assert!(matches!(
+            validation.check(IdPart::Code, "BRK.B"),
+            Err(IdError::DisallowedChar { ch: '.', position: 3, .. })
+        ));
+        let err = validation.check(IdPart::Code, "XTEST").unwrap_err();
+        assert_eq!(err.to_string(), "code \"XTEST\" is rejected: test codes are not allowed");
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "rkyv")]
+
+#[cfg(test)]
+mod tests {
+    use rkyv::rancor::Error;
+    use rkyv::Archived;
+    use static_id::static_id::*;
+    use static_id::{IdError, Symbol};
+
+    #[test]
+    fn test_symbol_round_trip() {
+        let symbol = Symbol::<16>::from("ESZ4");
+        let bytes = rkyv::to_bytes::<Error>(&symbol).unwrap();
+        // archived as POD, the same bytes as the symbol itself
+        assert_eq!(bytes.len(), 16);
+
+        let archived = rkyv::access::<Archived<Symbol<16>>, Error>(&bytes).unwrap();
+        assert_eq!(archived.as_bytes(), b"ESZ4");
+        assert_eq!(archived.len(), 4);
+        assert_eq!(rkyv::deserialize::<Symbol<16>, Error>(archived).unwrap(), symbol);
+    }
+
+    #[test]
+    fn test_resolve_archived_table() {
+        let ids: Vec<StaticId16x32> = ["AAPL", "MSFT", "005930"]
+            .iter()
+            .map(|code| StaticId16x32::from_str(code, "XNAS"))
+            .collect();
+        let table: Vec<IdCore16x32> = ids.iter().map(|id| id.get_id().clone()).collect();
+        let bytes = rkyv::to_bytes::<Error>(&table).unwrap();
+
+        let archived = rkyv::access::<Archived<Vec<IdCore16x32>>, Error>(&bytes).unwrap();
+        let resolved: Vec<StaticId16x32> = archived
+            .iter()
+            .map(|core| StaticId16x32::lookup_archived(core).unwrap())
+            .collect();
+        assert_eq!(resolved, ids);
+    }
+
+    #[test]
+    fn test_archived_ids_are_interned() {
+        let core = IdCore64x16 {
+            code: Symbol::from("archived-only"),
+            venue: Symbol::from("KRX"),
+        };
+        let bytes = rkyv::to_bytes::<Error>(&core).unwrap();
+        let archived = rkyv::access::<Archived<IdCore64x16>, Error>(&bytes).unwrap();
+
+        assert_eq!(StaticId64x16::lookup_archived(archived), None);
+        let id = StaticId64x16::try_from_archived(archived).unwrap();
+        assert_eq!(id.code_str(), "archived-only");
+        assert_eq!(StaticId64x16::lookup_archived(archived), Some(id));
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_rejected() {
+        let core = IdCore32x64 {
+            code: Symbol::from(&b"\xc3"[..]),
+            venue: Symbol::from("KRX"),
+        };
+        let bytes = rkyv::to_bytes::<Error>(&core).unwrap();
+        let archived = rkyv::access::<Archived<IdCore32x64>, Error>(&bytes).unwrap();
+        assert!(matches!(StaticId32x64::try_from_archived(archived), Err(IdError::InvalidUtf8 { .. })));
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use static_id::atomic::*;
+    use static_id::static_id::*;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_operations() {
+        let a = StaticId16x16::from_str("ESZ4", "CME");
+        let b = StaticId16x16::from_str("ESH5", "CME");
+        let cell = AtomicStaticId16x16::new(a);
+
+        assert_eq!(cell.load(Ordering::Relaxed), a);
+        cell.store(b, Ordering::Release);
+        assert_eq!(cell.load(Ordering::Acquire), b);
+        assert_eq!(cell.swap(a, Ordering::AcqRel), b);
+        assert_eq!(cell.compare_exchange(a, b, Ordering::AcqRel, Ordering::Acquire), Ok(a));
+        assert_eq!(cell.compare_exchange(a, b, Ordering::AcqRel, Ordering::Acquire), Err(b));
+        assert_eq!(format!("{:?}", cell), "ESH5@CME");
+        assert_eq!(cell.into_inner(), b);
+
+        assert_eq!(AtomicStaticId16x16::default().into_inner(), StaticId16x16::default());
+    }
+
+    #[test]
+    fn test_static_cell() {
+        static CURRENT: once_cell::sync::Lazy<AtomicStaticId> =
+            once_cell::sync::Lazy::new(|| AtomicStaticId::new(StaticId::from_str("CLZ4", "NYMEX")));
+        CURRENT.store(StaticId::from_str("CLF5", "NYMEX"), Ordering::Release);
+        assert_eq!(CURRENT.load(Ordering::Acquire).code_str(), "CLF5");
+    }
+
+    #[test]
+    fn test_concurrent_rolls() {
+        let months: Vec<StaticId32x16> = (0..8).map(|i| StaticId32x16::from_str(&format!("ES{}", i), "CME")).collect();
+        let cell = Arc::new(AtomicStaticId32x16::new(months[0]));
+
+        // each thread rolls the contract forward by one with compare_exchange_weak
+        let handles: Vec<_> = (0..7)
+            .map(|_| {
+                let cell = cell.clone();
+                let months = months.clone();
+                std::thread::spawn(move || {
+                    let mut current = cell.load(Ordering::Acquire);
+                    loop {
+                        let position = months.iter().position(|&m| m == current).unwrap();
+                        let next = months[position + 1];
+                        match cell.compare_exchange_weak(current, next, Ordering::AcqRel, Ordering::Acquire) {
+                            Ok(_) => break,
+                            Err(actual) => current = actual,
+                        }
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(cell.load(Ordering::Acquire), months[7]);
+    }
+}
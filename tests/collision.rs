@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use static_id::collision::*;
+    use static_id::static_id::*;
+    use static_id::Truncation;
+
+    const LONG_A: &str = "SPXW  241220P05000000";
+    const LONG_B: &str = "SPXW  241220P05000001";
+
+    #[test]
+    fn test_record() {
+        StaticId16x16::set_collision_detection(CollisionDetection::Record);
+        let a = StaticId16x16::from_str(LONG_A, "CBOE");
+        let _ = StaticId16x16::from_str(LONG_A, "CBOE");
+        assert!(StaticId16x16::collisions().is_empty());
+
+        let b = StaticId16x16::try_from_str(LONG_B, "CBOE").unwrap();
+        let exact = StaticId16x16::from_str(a.code_str(), "CBOE");
+        let _ = StaticId16x16::from_str(LONG_B, "CBOE");
+        assert_eq!(a, b);
+        assert_eq!(a, exact);
+
+        let collisions = StaticId16x16::collisions();
+        assert_eq!(collisions.len(), 2);
+        assert_eq!(collisions[0].input, (LONG_B.to_string(), "CBOE".to_string()));
+        assert_eq!(collisions[1].input.0, a.code_str());
+        assert!(collisions.iter().all(|collision| collision.first.0 == LONG_A));
+        assert!(collisions[0].to_string().contains("both intern to"));
+    }
+
+    #[test]
+    fn test_hash_suffix_avoids_collisions() {
+        StaticId16x64::set_truncation(Truncation::HashSuffix);
+        StaticId16x64::set_collision_detection(CollisionDetection::Log);
+        assert_ne!(StaticId16x64::from_str(LONG_A, "CBOE"), StaticId16x64::from_str(LONG_B, "CBOE"));
+        assert!(StaticId16x64::collisions().is_empty());
+    }
+
+    #[test]
+    fn test_panic() {
+        StaticId16x0::set_collision_detection(CollisionDetection::Panic);
+        let _ = StaticId16x0::from_str(LONG_A);
+        let result = std::panic::catch_unwind(|| StaticId16x0::from_str(LONG_B));
+        let message = result.unwrap_err();
+        assert!(message.downcast_ref::<String>().unwrap().contains(LONG_B));
+
+        // the panic leaves the cache usable
+        assert_eq!(StaticId16x0::collisions().len(), 1);
+        assert_eq!(StaticId16x0::from_str("MSFT").code_str(), "MSFT");
+        assert!(StaticId16x0::try_from_str(LONG_B).is_ok());
+    }
+}
@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use static_id::compact::*;
+    use static_id::static_id::*;
+    use static_id::SerdeRepr;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::mem::size_of;
+
+    fn hash_of<T: Hash>(value: &T) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        assert_eq!(size_of::<CompactId16x16>(), 4);
+        assert_eq!(size_of::<[CompactId16x16; 16]>(), 64);
+
+        let id = StaticId16x16::from_str("ESZ4", "CME");
+        let compact = CompactId16x16::from(id);
+        assert_eq!(compact.index(), id.index());
+        assert_eq!(StaticId16x16::from(compact), id);
+        assert_eq!(compact.code_str(), "ESZ4");
+        assert_eq!(compact.venue_str(), "CME");
+        assert_eq!(compact, CompactId16x16::from_str("ESZ4", "CME"));
+        assert_ne!(compact, CompactId16x16::from_str("NQZ4", "CME"));
+        assert_eq!(CompactId16x16::from_index(compact.index()), Some(compact));
+        assert_eq!(CompactId16x16::from_index(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_behaves_like_static_id() {
+        let id = StaticId32x64::from_str("A@B", "XNAS");
+        let compact = CompactId32x64::from(id);
+        assert_eq!(compact.to_string(), id.to_string());
+        assert_eq!(format!("{:?}", compact), format!("{:?}", id));
+        assert_eq!(hash_of(&compact), hash_of(&id));
+
+        let json = serde_json::to_string(&compact).unwrap();
+        assert_eq!(json, serde_json::to_string(&id).unwrap());
+        assert_eq!(serde_json::from_str::<CompactId32x64>(&json).unwrap(), compact);
+
+        StaticId32x64::set_serde_repr(Some(SerdeRepr::Tuple));
+        assert_eq!(serde_json::to_string(&compact).unwrap(), r#"["A@B","XNAS"]"#);
+        StaticId32x64::set_serde_repr(None);
+    }
+
+    #[test]
+    fn test_from_index_across_segments() {
+        let ids: Vec<StaticId64x32> = (0..1000).map(|i| StaticId64x32::from_str(&i.to_string(), "X")).collect();
+        for id in &ids {
+            let compact = CompactId64x32::from(*id);
+            assert_eq!(compact.to_static_id(), *id);
+            assert_eq!(StaticId64x32::from_index(id.index()), Some(*id));
+        }
+    }
+}
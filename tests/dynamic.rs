@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use static_id::dynamic::*;
+    use static_id::static_id::*;
+    use static_id::{IdError, IdFormat, SerdeRepr};
+
+    #[test]
+    fn test_no_truncation() {
+        let a = "SPXW  241220P05000000.WEEKLY.EXPIRY.AM.SETTLED.A";
+        let b = "SPXW  241220P05000000.WEEKLY.EXPIRY.AM.SETTLED.B";
+        let (ida, idb) = (DynamicId::from_str(a, "CBOE"), DynamicId::from_str(b, "CBOE"));
+        assert_ne!(ida, idb);
+        assert_eq!(ida.code_str(), a);
+        assert_eq!(ida.len(), a.len() + 4);
+        assert_eq!(DynamicId::lookup(a, "CBOE"), Some(ida));
+        assert_eq!(DynamicId::lookup(&a[..32], "CBOE"), None);
+
+        // the same text as a truncating type collides
+        assert_eq!(StaticId32x16::from_str(a, "CBOE"), StaticId32x16::from_str(b, "CBOE"));
+    }
+
+    #[test]
+    fn test_same_api() {
+        let id = DynamicId::from_str("AAPL", "NASDAQ");
+        assert_eq!(id, DynamicId::from_bytes(b"AAPL", b"NASDAQ"));
+        assert_eq!(id, DynamicId::from_combined_str("AAPL@NASDAQ"));
+        assert_eq!(DynamicId::try_from_str("AAPL", "NASDAQ"), Ok(id));
+        assert_eq!(DynamicId::from_index(id.index()), Some(id));
+        assert_eq!(id.fingerprint(), StaticId::from_str("AAPL", "NASDAQ").fingerprint());
+        assert_eq!(id.fingerprint128(), StaticId::from_str("AAPL", "NASDAQ").fingerprint128());
+        assert_eq!(id.display_with(IdFormat::new(':')).to_string(), "AAPL:NASDAQ");
+        assert_eq!(format!("{:?}", id), "AAPL@NASDAQ");
+        assert!(DynamicId::default().is_empty());
+
+        assert!(matches!(
+            DynamicId::try_from_bytes(b"\xFF", b"X"),
+            Err(IdError::InvalidUtf8 { .. })
+        ));
+        assert_eq!(DynamicId::from_bytes(b"A\xFF", b"X").code_str(), "A\u{FFFD}");
+    }
+
+    #[test]
+    fn test_queries() {
+        let long = "ZQ241220P05000000.WEEKLY.EXPIRY.AM.SETTLED";
+        let a = DynamicId::from_str(long, "DQA");
+        let b = DynamicId::from_str(long, "DQB");
+        let c = DynamicId::from_str("ZQX", "DQA");
+        let d = DynamicId::from_str("YQ", "DQA");
+
+        assert_eq!(DynamicId::ids_for_code(long), vec![a, b]);
+        assert_eq!(DynamicId::ids_for_venue("DQA"), vec![a, c, d]);
+        assert_eq!(DynamicId::search_prefix("ZQ", Some("DQA")), vec![a, c]);
+        assert_eq!(DynamicId::search_glob("ZQ*WEEKLY*"), vec![a, b]);
+        assert_eq!(DynamicId::search_glob("?Q*@DQA"), vec![d, a, c]);
+        let escaped = DynamicId::from_str("ZQ@DQA", "DQC");
+        assert_eq!(DynamicId::search_glob(r"ZQ\@DQ*@DQC"), vec![escaped]);
+
+        assert_eq!(DynamicId::lookup_or_suggest(long, "DQB", 2), Ok(b));
+        let suggestions = DynamicId::lookup_or_suggest("ZQ241220P05000000.WEEKLY.EXPIRY.PM.SETTLED", "DQB", 1);
+        assert_eq!(suggestions, Err(vec![b]));
+        assert_eq!(DynamicId::similar("ZQY", "DQA", 1), vec![(c, 1)]);
+    }
+
+    #[test]
+    fn test_serde() {
+        let id = DynamicId::from_str("A@B", "X");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#""A\\@B@X""#);
+        assert_eq!(serde_json::from_str::<DynamicId>(&json).unwrap(), id);
+
+        DynamicId::set_serde_repr(Some(SerdeRepr::Tuple));
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#"["A@B","X"]"#);
+        assert_eq!(serde_json::from_str::<DynamicId>(&json).unwrap(), id);
+        DynamicId::set_serde_repr(None);
+    }
+
+    #[test]
+    fn test_concurrent() {
+        let ids: Vec<Vec<DynamicId>> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| (0..200).map(|i| DynamicId::from_str(&format!("C{}", i), "V")).collect()))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+        assert!(ids.windows(2).all(|pair| pair[0] == pair[1]));
+        assert!(DynamicId::cache_text_bytes() < DynamicId::cache_len() * 8);
+    }
+}
@@ -0,0 +1,64 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::{IdError, IdFormat};
+
+    const ADVERSARIAL: [&str; 12] = ["", "@", "\\", "@@", "\\@", "@\\", "A@B", "A\\B", "\\\\@@", "a-b", "-", "삼성@"];
+
+    #[test]
+    fn test_serde_round_trip() {
+        for code in ADVERSARIAL {
+            for venue in ADVERSARIAL {
+                let id = StaticId::from_str(code, venue);
+                let serialized = serde_json::to_string(&id).unwrap();
+                let deserde: StaticId = serde_json::from_str(&serialized).unwrap();
+                assert_eq!(id, deserde, "via {}", serialized);
+                assert_eq!(StaticId::from_combined_str(&id.to_string()), id);
+            }
+        }
+    }
+
+    #[test]
+    fn test_serde_round_trip_custom_format() {
+        StaticId16x64::set_format(IdFormat::new('-'));
+        for code in ADVERSARIAL {
+            for venue in ADVERSARIAL {
+                let id = StaticId16x64::from_str(code, venue);
+                let serialized = serde_json::to_string(&id).unwrap();
+                let deserde: StaticId16x64 = serde_json::from_str(&serialized).unwrap();
+                assert_eq!(id, deserde, "via {}", serialized);
+            }
+        }
+    }
+
+    #[test]
+    fn test_separator_in_code_is_not_ambiguous() {
+        let id = StaticId64x16::from_str("A@B", "C");
+        let other = StaticId64x16::from_str("A", "B@C");
+
+        assert_eq!(id.to_string(), "A\\@B@C");
+        assert_eq!(other.to_string(), "A@B\\@C");
+        assert_eq!(StaticId64x16::from_combined_str("A\\@B@C"), id);
+        assert_eq!(StaticId64x16::from_combined_str("A@B\\@C"), other);
+    }
+
+    #[test]
+    fn test_malformed_input_is_rejected() {
+        for malformed in ["A\\B@C", "A@B@C", "A@B\\", "A\\@B", "\\"] {
+            assert!(serde_json::from_str::<StaticId32x32>(&format!("{:?}", malformed)).is_err(), "{}", malformed);
+        }
+        assert!(matches!(
+            StaticId32x32::try_from_combined_str("A@B@C"),
+            Err(IdError::InvalidEscape { position: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_infallible_parser_keeps_malformed_input() {
+        assert_eq!(StaticId32x32::from_combined_str("A\\B@X"), StaticId32x32::from_str("A\\B", "X"));
+        assert_eq!(StaticId32x32::from_combined_str("A@B@C"), StaticId32x32::from_str("A", "B@C"));
+        assert_eq!(StaticId32x32::from_combined_str("A@B\\"), StaticId32x32::from_str("A", "B\\"));
+        assert_eq!(StaticId32x32::from_combined_str("A\\@B@C"), StaticId32x32::from_str("A@B", "C"));
+        assert!(std::panic::catch_unwind(|| StaticId32x32::from_combined_str("AB")).is_err());
+    }
+}
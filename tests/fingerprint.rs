@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use static_id::fingerprint::{fingerprint128, fingerprint64};
+    use static_id::static_id::*;
+
+    #[test]
+    fn test_pinned_values() {
+        // must never change: fingerprints are stored and shared outside the process
+        let id = StaticId::from_str("AAPL", "NASDAQ");
+        assert_eq!(id.fingerprint(), 0x624e_789b_54df_8798);
+        assert_eq!(id.fingerprint128(), 0xf9e3_8aca_3634_dd79_62e2_a342_4ec4_ad98);
+        assert_eq!(StaticId16x0::from_str("005930").fingerprint(), 0x73cb_471b_1e3b_b42d);
+    }
+
+    #[test]
+    fn test_independent_of_type() {
+        let small = StaticId16x16::from_str("ESZ4", "CME");
+        let large = StaticId64x64::from_str("ESZ4", "CME");
+        assert_eq!(small.fingerprint(), large.fingerprint());
+        assert_eq!(small.fingerprint128(), large.fingerprint128());
+        assert_eq!(small.fingerprint(), fingerprint64("ESZ4", "CME"));
+        assert_eq!(small.fingerprint128(), fingerprint128("ESZ4", "CME"));
+        assert_eq!(
+            StaticId32x0::from_str("ESZ4").fingerprint(),
+            StaticId32x16::from_str("ESZ4", "").fingerprint()
+        );
+    }
+
+    #[test]
+    fn test_distinct_ids() {
+        let ids = [
+            StaticId32x32::from_str("AB", "C"),
+            StaticId32x32::from_str("A", "BC"),
+            StaticId32x32::from_str("ABC", ""),
+            StaticId32x32::from_str("", "ABC"),
+        ];
+        for (i, a) in ids.iter().enumerate() {
+            for b in &ids[i + 1..] {
+                assert_ne!(a.fingerprint(), b.fingerprint());
+                assert_ne!(a.fingerprint128(), b.fingerprint128());
+            }
+        }
+        assert_eq!(ids[0].fingerprint(), ids[0].entry().fingerprint());
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::IdFormat;
+
+    #[test]
+    fn test_per_type_format() {
+        StaticId16x32::set_format(IdFormat::new('-'));
+        assert_eq!(StaticId16x32::format(), IdFormat::new('-'));
+
+        let id = StaticId16x32::from_combined_str("BTC-USDT");
+        assert_eq!(id, StaticId16x32::from_str("BTC", "USDT"));
+        assert_eq!(id.to_string(), "BTC-USDT");
+        assert_eq!(format!("{:?}", id), "BTC-USDT");
+
+        let serialized = serde_json::to_string(&id).unwrap();
+        assert_eq!(serialized, "\"BTC-USDT\"");
+        let deserde: StaticId16x32 = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(id, deserde);
+        assert!(serde_json::from_str::<StaticId16x32>("\"BTC@USDT\"").is_err());
+
+        assert_eq!(StaticId16x32::search_glob("BTC-US*"), vec![id]);
+    }
+
+    #[test]
+    fn test_per_call_format() {
+        let reuters = IdFormat::new('.');
+        let internal = IdFormat::new(':');
+
+        let id = StaticId::from_combined_str_with("AAPL.O", reuters);
+        assert_eq!(id, StaticId::try_from_combined_str_with("AAPL:O", internal).unwrap());
+        assert_eq!(id.display_with(reuters).to_string(), "AAPL.O");
+        assert_eq!(id.display_with(internal).to_string(), "AAPL:O");
+        // the type's own format is untouched
+        assert_eq!(id.to_string(), "AAPL@O");
+        assert!(StaticId::try_from_combined_str_with("AAPL@O", reuters).is_err());
+    }
+
+    #[test]
+    fn test_default_format() {
+        assert_eq!(StaticId32x64::format(), IdFormat::DEFAULT);
+        let id = StaticId32x64::from_combined_str("AAPL@NASDAQ");
+        assert_eq!(id.to_string(), "AAPL@NASDAQ");
+    }
+}
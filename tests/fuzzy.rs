@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+
+    #[test]
+    fn test_lookup_does_not_intern() {
+        let id = StaticId64x16::from_str("AAPL", "NASDAQ");
+        let len = StaticId64x16::cache_len();
+
+        assert_eq!(StaticId64x16::lookup("AAPL", "NASDAQ"), Some(id));
+        assert_eq!(StaticId64x16::lookup("AAPX", "NASDAQ"), None);
+        assert_eq!(StaticId64x16::cache_len(), len);
+    }
+
+    #[test]
+    fn test_similar() {
+        let aapl = StaticId::from_str("AAPL", "NASDAQ");
+        let amzn = StaticId::from_str("AMZN", "NASDAQ");
+        let aapl_nyse = StaticId::from_str("AAPL", "NYSE");
+
+        let ranked = StaticId::similar("APPL", "NASDAQ", 3);
+        assert_eq!(ranked[0], (aapl, 1));
+        assert_eq!(ranked[1], (amzn, 3));
+        assert_eq!(ranked[2].0, aapl_nyse);
+
+        assert_eq!(StaticId::similar("aapl", "nasdaq", 1), vec![(aapl, 0)]);
+        assert!(StaticId::similar("APPL", "NASDAQ", 0).is_empty());
+    }
+
+    #[test]
+    fn test_lookup_or_suggest() {
+        let es = StaticId16x16::from_str("ESZ4", "CME");
+        let _ = StaticId16x16::from_str("NQZ4", "CME");
+        let len = StaticId16x16::cache_len();
+
+        assert_eq!(StaticId16x16::lookup_or_suggest("ESZ4", "CME", 2), Ok(es));
+        let suggestions = StaticId16x16::lookup_or_suggest("ESZ5", "CME", 2).unwrap_err();
+        assert_eq!(suggestions[0], es);
+        assert_eq!(StaticId16x16::cache_len(), len);
+    }
+}
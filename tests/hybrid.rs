@@ -0,0 +1,96 @@
+#[cfg(test)]
+mod tests {
+    use static_id::hybrid::*;
+    use static_id::static_id::*;
+    use static_id::Truncation;
+    use std::collections::HashSet;
+    use std::mem::size_of;
+
+    #[test]
+    fn test_inline_ids() {
+        assert_eq!(size_of::<HybridId>(), 8);
+
+        let cache_len = StaticId16x16::cache_len();
+        let ids = [
+            HybridId16x16::from_str("AAPL", "KRX"),
+            HybridId16x16::from_str("IBM", "NYSE"),
+            HybridId16x16::from_str("", ""),
+            HybridId16x16::from_str("삼성", ""),
+            HybridId16x16::from_str("T", "X"),
+        ];
+        for id in ids {
+            assert!(id.is_inline(), "{}", id);
+        }
+        assert_eq!(StaticId16x16::cache_len(), cache_len, "inline ids are not interned");
+
+        assert_eq!(ids[0].code_str(), "AAPL");
+        assert_eq!(ids[0].venue_str(), "KRX");
+        assert_eq!(ids[3].code_str(), "삼성");
+        assert_eq!(ids[4].to_string(), "T@X");
+        assert_eq!(ids[0], HybridId16x16::from_str("AAPL", "KRX"));
+        assert_ne!(ids[0], HybridId16x16::from_str("AAP", "LKRX"));
+    }
+
+    #[test]
+    fn test_interned_fallback() {
+        let id = HybridId32x16::from_str("AAPL", "XNAS");
+        assert!(!id.is_inline());
+        assert_eq!(id.code_str(), "AAPL");
+        assert_eq!(id.venue_str(), "XNAS");
+        assert_eq!(id, HybridId32x16::from_str("AAPL", "XNAS"));
+        assert_eq!(id.to_static_id(), StaticId32x16::from_str("AAPL", "XNAS"));
+    }
+
+    #[test]
+    fn test_conversions_are_canonical() {
+        for (code, venue) in [("AAPL", "KRX"), ("KR7005930003", "KRX"), ("MSFT", "XNAS")] {
+            let hybrid = HybridId::from_str(code, venue);
+            let id = StaticId::from_str(code, venue);
+            assert_eq!(HybridId::from(id), hybrid);
+            assert_eq!(StaticId::from(hybrid), id);
+        }
+
+        let set: HashSet<HybridId> = [
+            HybridId::from_str("AAPL", "KRX"),
+            HybridId::from(StaticId::from_str("AAPL", "KRX")),
+            HybridId::from_str("KR7005930003", "KRX"),
+            HybridId::from(StaticId::from_str("KR7005930003", "KRX")),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn test_types_without_venue() {
+        let id = HybridId64x0::from_str("005930", "ignored");
+        assert!(id.is_inline());
+        assert_eq!(id.venue_str(), "");
+        assert_eq!(id.to_static_id(), StaticId64x0::from_str("005930"));
+    }
+
+    #[test]
+    fn test_truncation_policy() {
+        StaticId16x32::set_truncation(Truncation::Reject);
+        assert!(std::panic::catch_unwind(|| HybridId16x32::from_str("SPXW241220P05000000", "CBOE")).is_err());
+        assert!(HybridId16x32::try_from_str("SPXW241220P05000000", "CBOE").is_err());
+        assert!(serde_json::from_str::<HybridId16x32>(r#""SPXW241220P05000000@CBOE""#).is_err());
+        assert_eq!(HybridId16x32::from_str("SPXW241220P05000", "CBOE").code_str(), "SPXW241220P05000");
+
+        StaticId16x64::set_truncation(Truncation::HashSuffix);
+        let a = HybridId16x64::from_str("SPXW241220P05000000", "CBOE");
+        let b = HybridId16x64::from_str("SPXW241220P05000001", "CBOE");
+        assert_ne!(a, b);
+        assert_eq!(a.to_static_id(), StaticId16x64::from_str("SPXW241220P05000000", "CBOE"));
+    }
+
+    #[test]
+    fn test_serde() {
+        let ids = vec![HybridId::from_str("A@B", "X"), HybridId::from_str("KR7005930003", "KRX")];
+        let json = serde_json::to_string(&ids).unwrap();
+        assert_eq!(json, r#"["A\\@B@X","KR7005930003@KRX"]"#);
+        let back: Vec<HybridId> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, ids);
+        assert!(serde_json::from_str::<HybridId>(r#""AAPL""#).is_err());
+    }
+}
@@ -0,0 +1,30 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_truncated_input_hits_the_stored_id() {
+        let long = "0123456789ABCDEF-overflow";
+        let truncated = StaticId16x16::from_str(long, "XKRX");
+        assert_eq!(truncated.code_str(), "0123456789ABCDEF");
+        assert_eq!(StaticId16x16::from_str("0123456789ABCDEF", "XKRX"), truncated);
+        assert_eq!(StaticId16x16::lookup(long, "XKRX"), Some(truncated));
+
+        // a symbol ends at the first 0xFF byte
+        let marked = StaticId16x16::from_bytes(b"AB\xffCD", b"XKRX");
+        assert_eq!(marked, StaticId16x16::from_str("AB", "XKRX"));
+    }
+
+    #[test]
+    fn test_precomputed_hash() {
+        let a = StaticId32x16::from_str("AAPL", "NASDAQ");
+        let b = StaticId32x16::from_str("MSFT", "NASDAQ");
+        assert_ne!(a.entry().precomputed_hash(), b.entry().precomputed_hash());
+        assert_eq!(StaticId32x16::from_str("AAPL", "NASDAQ").entry().precomputed_hash(), a.entry().precomputed_hash());
+
+        let set: HashSet<_> = [a, b, a].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&StaticId32x16::from_str("MSFT", "NASDAQ")));
+    }
+}
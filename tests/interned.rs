@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use static_id::interned::*;
+    use static_id::static_id::*;
+    use std::collections::HashSet;
+    use std::fmt;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    struct StrategyConfig {
+        name: String,
+        lookback: u32,
+    }
+
+    impl fmt::Display for StrategyConfig {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}({})", self.name, self.lookback)
+        }
+    }
+
+    impl Intern for StrategyConfig {
+        fn interner() -> &'static Interner<Self> {
+            static INTERNER: Interner<StrategyConfig> = Interner::new();
+            &INTERNER
+        }
+    }
+
+    fn config(name: &str, lookback: u32) -> StrategyConfig {
+        StrategyConfig { name: name.to_string(), lookback }
+    }
+
+    #[test]
+    fn test_handle() {
+        let a = Interned::new(config("momentum", 20));
+        let b = config("momentum", 20).intern();
+        let c = Interned::new(config("momentum", 50));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(Interned::as_ptr(a), Interned::as_ptr(b));
+        assert!(std::ptr::eq(Interned::as_static(a), Interned::as_static(b)));
+        assert_eq!(a.lookback, 20);
+        assert_eq!(std::mem::size_of::<Interned<StrategyConfig>>(), std::mem::size_of::<usize>());
+
+        assert_eq!(a.to_string(), "momentum(20)");
+        assert_eq!(format!("{:?}", a), format!("{:?}", config("momentum", 20)));
+
+        let set: HashSet<_> = [a, b, c].into_iter().collect();
+        assert_eq!(set.len(), 2);
+        assert_eq!(StrategyConfig::interner().get(&config("momentum", 50)), Some(c));
+    }
+
+    #[test]
+    fn test_serde() {
+        let a = Interned::new(config("carry", 5));
+        let json = serde_json::to_string(&a).unwrap();
+        assert_eq!(json, serde_json::to_string(&config("carry", 5)).unwrap());
+
+        let back: Interned<StrategyConfig> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, a);
+    }
+
+    #[test]
+    fn test_local_interner() {
+        let interner: Interner<String> = Interner::new();
+        let a = interner.intern_borrowed("ESZ4");
+        let b = interner.intern("ESZ4".to_string());
+        assert_eq!(a, b);
+        assert_eq!(interner.get("ESZ4"), Some(a));
+        assert_eq!(interner.get("ESH5"), None);
+        assert_eq!(interner.len(), 1);
+
+        let mut set = InternSet::new();
+        let c = set.intern(7u64);
+        assert_eq!(set.intern(7u64), c);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![c]);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct Flaky(u32);
+
+    impl std::hash::Hash for Flaky {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            assert_ne!(self.0, 0, "unhashable");
+            self.0.hash(state);
+        }
+    }
+
+    #[test]
+    fn test_interner_recovers_from_poison() {
+        let interner: Interner<Flaky> = Interner::new();
+        let a = interner.intern(Flaky(1));
+        let panicked = std::panic::catch_unwind(|| interner.intern(Flaky(0)));
+        assert!(panicked.is_err());
+        assert_eq!(interner.intern(Flaky(1)), a);
+        assert_eq!(interner.len(), 1);
+    }
+
+    #[test]
+    fn test_static_id_handle() {
+        let a = StaticId::from_str("AAPL", "NASDAQ");
+        let b = StaticId::from_str("AAPL", "NASDAQ");
+        assert!(std::ptr::eq(a.entry(), b.entry()));
+        assert_eq!(a.entry().code.as_str(), "AAPL");
+        assert_eq!(a.entry().index(), a.index());
+    }
+}
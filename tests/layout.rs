@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::{IdError, IdPart, Symbol};
+    use std::mem::{align_of, offset_of, size_of};
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(size_of::<Symbol<16>>(), 16);
+        assert_eq!(align_of::<Symbol<16>>(), 1);
+        assert_eq!(size_of::<IdCore16x0>(), 16);
+        assert_eq!(size_of::<IdCore32x64>(), 96);
+        assert_eq!(align_of::<IdCore32x64>(), 1);
+        assert_eq!(offset_of!(IdCore32x64, code), 0);
+        assert_eq!(offset_of!(IdCore32x64, venue), 32);
+        assert_eq!(IdCore64x16::SIZE, size_of::<IdCore64x16>());
+
+        let symbol = Symbol::<8>::from("ABC");
+        assert_eq!(symbol.as_raw_bytes(), b"ABC\xff\xff\xff\xff\xff");
+        assert_eq!(Symbol::<8>::try_ref_from_raw_bytes(b"ABC\xff\xff\xff\xff\xff", IdPart::Code), Ok(&symbol));
+        assert_eq!(
+            Symbol::<8>::try_ref_from_raw_bytes(b"ABC\xffD\xff\xff\xff", IdPart::Venue),
+            Err(IdError::InvalidPadding { part: IdPart::Venue })
+        );
+        assert_eq!(
+            Symbol::<8>::try_ref_from_raw_bytes(b"\xc3\xff\xff\xff\xff\xff\xff\xff", IdPart::Code),
+            Err(IdError::InvalidUtf8 { part: IdPart::Code })
+        );
+    }
+
+    #[test]
+    fn test_ring_buffer_round_trip() {
+        let ids = [
+            StaticId16x32::from_str("AAPL", "NASDAQ"),
+            StaticId16x32::from_str("005930", "KRX"),
+            StaticId16x32::from_str("", ""),
+        ];
+        // one byte of header so the cores are not aligned to anything
+        let mut region = vec![0u8];
+        for id in &ids {
+            region.extend_from_slice(id.get_id().as_bytes());
+        }
+
+        let cores = IdCore16x32::try_slice_from_bytes(&region[1..]).unwrap();
+        assert_eq!(cores.len(), 3);
+        assert_eq!(IdCore16x32::slice_as_bytes(cores), &region[1..]);
+        for (core, id) in cores.iter().zip(&ids) {
+            assert_eq!(core, id.get_id());
+        }
+
+        let raw = &region[1 + IdCore16x32::SIZE..1 + 2 * IdCore16x32::SIZE];
+        assert_eq!(StaticId16x32::try_from_core_bytes(raw).unwrap(), ids[1]);
+    }
+
+    #[test]
+    fn test_raw_core_from_foreign_writer() {
+        // as written by a C++ process with memset(0xFF) and memcpy
+        let mut raw = [0xffu8; IdCore64x0::SIZE];
+        raw[..9].copy_from_slice(b"KR7005930");
+        let id = StaticId64x0::try_from_core_bytes(&raw).unwrap();
+        assert_eq!(id, StaticId64x0::from_str("KR7005930"));
+    }
+
+    #[test]
+    fn test_invalid_raw_cores() {
+        let mut raw = [0xffu8; IdCore16x16::SIZE];
+        assert_eq!(
+            IdCore16x16::try_ref_from_bytes(&raw[1..]),
+            Err(IdError::SizeMismatch { expected: 32, found: 31 })
+        );
+        assert_eq!(
+            IdCore16x16::try_slice_from_bytes(&[0xff; 33]),
+            Err(IdError::SliceSizeMismatch { core_size: 32, found: 33 })
+        );
+        assert_eq!(
+            IdCore16x16::try_ref_from_bytes(&[0xff; 64]).unwrap_err().to_string(),
+            "raw core has 64 bytes, expected 32"
+        );
+        assert!(IdError::SliceSizeMismatch { core_size: 32, found: 33 }.to_string().contains("a multiple of 32"));
+
+        raw[16] = b'A';
+        raw[18] = b'B';
+        assert_eq!(
+            IdCore16x16::try_ref_from_bytes(&raw),
+            Err(IdError::InvalidPadding { part: IdPart::Venue })
+        );
+
+        // C strings are NUL-terminated, not 0xFF-padded: the NULs are part of the code
+        let mut raw = [0xffu8; IdCore16x16::SIZE];
+        raw[..2].copy_from_slice(b"A\0");
+        let id = StaticId16x16::try_from_core_bytes(&raw).unwrap();
+        assert_eq!(id.code_str(), "A\0");
+        assert_ne!(id, StaticId16x16::from_str("A", ""));
+
+        let mut raw = [0xffu8; IdCore16x16::SIZE];
+        raw[0] = 0xc3;
+        assert_eq!(
+            IdCore16x16::try_ref_from_bytes(&raw),
+            Err(IdError::InvalidUtf8 { part: IdPart::Code })
+        );
+    }
+}
@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use static_id::map::*;
+    use static_id::static_id::*;
+    use std::collections::HashMap;
+    use std::collections::hash_map::Entry;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_operations() {
+        let map = IdMap16x16::with_shards(3);
+        assert_eq!(map.shards().len(), 4);
+
+        let a = StaticId16x16::from_str("ESZ4", "CME");
+        let b = StaticId16x16::from_str("NQZ4", "CME");
+        assert_eq!(map.insert(a, 1), None);
+        assert_eq!(map.insert(a, 2), Some(1));
+        assert_eq!(map.get(&a), Some(2));
+        assert_eq!(map.get(&b), None);
+        assert_eq!(map.get_with(&a, |v| v * 10), Some(20));
+        assert!(map.contains_key(&a));
+
+        map.entry(b, |entry| match entry {
+            Entry::Occupied(_) => unreachable!(),
+            Entry::Vacant(vacant) => {
+                vacant.insert(5);
+            }
+        });
+        map.entry(b, |entry| *entry.or_insert(0) += 1);
+        assert_eq!(map.get(&b), Some(6));
+        assert_eq!(map.len(), 2);
+
+        map.for_each_mut(|_, v| *v *= 100);
+        let mut snapshot = map.snapshot();
+        snapshot.sort_by_key(|(_, v)| *v);
+        assert_eq!(snapshot, vec![(a, 200), (b, 600)]);
+
+        map.retain(|id, _| *id == a);
+        assert_eq!(map.remove(&b), None);
+        assert_eq!(map.remove(&a), Some(200));
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_matches_hashmap() {
+        let map = IdMap::new();
+        let mut expected = HashMap::new();
+        for i in 0..1000 {
+            let id = StaticId::from_str(&format!("K{}", i % 300), "X");
+            map.entry(id, |entry| *entry.or_insert(0) += i);
+            *expected.entry(id).or_insert(0) += i;
+        }
+        let mut count = 0;
+        map.for_each(|id, v| {
+            assert_eq!(expected.get(id), Some(v));
+            count += 1;
+        });
+        assert_eq!(count, expected.len());
+        let owned: HashMap<_, _> = map.into_iter().collect();
+        assert_eq!(owned, expected);
+    }
+
+    #[test]
+    fn test_multi_thread() {
+        let ids: Vec<StaticId32x16> = (0..64).map(|i| StaticId32x16::from_str(&i.to_string(), "X")).collect();
+        let map = Arc::new(IdMap32x16::new());
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let (ids, map) = (ids.clone(), map.clone());
+                thread::spawn(move || {
+                    for _ in 0..1000 {
+                        for id in &ids {
+                            map.entry(*id, |entry| *entry.or_insert(0u64) += 1);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for t in threads {
+            t.join().unwrap();
+        }
+        for id in &ids {
+            assert_eq!(map.get(id), Some(8000));
+        }
+    }
+}
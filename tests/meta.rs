@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+    use static_id::meta::*;
+    use static_id::static_id::*;
+    use static_id::{CharClasses, IdError, Validation};
+    use std::mem::size_of;
+
+    #[derive(Debug, PartialEq)]
+    struct InstrumentInfo {
+        tick_size: f64,
+        lot_size: u32,
+        currency: &'static str,
+    }
+
+    #[test]
+    fn test_same_handle() {
+        assert_eq!(size_of::<MetaId16x16<InstrumentInfo>>(), 8);
+
+        let registry = MetaRegistry16x16::new();
+        let id = StaticId16x16::from_str("005930", "KRX");
+        let handle = registry
+            .register(id, InstrumentInfo { tick_size: 100.0, lot_size: 1, currency: "KRW" })
+            .unwrap();
+
+        assert_eq!(handle.id(), id);
+        assert_eq!(handle.code_str(), "005930");
+        assert_eq!(handle.to_string(), "005930@KRX");
+        assert_eq!(handle.meta().lot_size, 1);
+
+        let by_id = registry.get(id).unwrap();
+        let by_str = registry.lookup("005930", "KRX").unwrap();
+        assert_eq!(by_id, handle);
+        assert_eq!(by_str, handle);
+        assert!(std::ptr::eq(by_str.meta(), handle.meta()));
+        assert_eq!(StaticId16x16::from(by_str), id);
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.ids(), vec![handle]);
+    }
+
+    #[test]
+    fn test_unregistered() {
+        let registry: MetaRegistry32x16<u32> = MetaRegistry32x16::default();
+        let cache_len = StaticId32x16::cache_len();
+        assert_eq!(registry.lookup("NOPE", "KRX"), None);
+        assert_eq!(StaticId32x16::cache_len(), cache_len, "lookup does not intern");
+
+        let id = StaticId32x16::from_str("NOPE", "KRX");
+        assert_eq!(registry.get(id), None);
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn test_registered_once() {
+        static REGISTRY: MetaRegistry64x0<usize> = MetaRegistry64x0::new();
+        let results: Vec<_> = (0..8)
+            .map(|t| std::thread::spawn(move || REGISTRY.register_str("ESZ4", "", t)))
+            .map(|handle| handle.join().unwrap())
+            .collect();
+
+        let winners: Vec<_> = results.iter().filter(|result| result.is_ok()).collect();
+        assert_eq!(winners.len(), 1);
+        let handle = *winners[0].as_ref().unwrap();
+        for result in &results {
+            let other = match result {
+                Ok(other) => *other,
+                Err(err) => err.registered().unwrap(),
+            };
+            assert_eq!(other, handle);
+            assert_eq!(other.meta(), handle.meta());
+        }
+        assert_eq!(REGISTRY.lookup("ESZ4", ""), Some(handle));
+    }
+
+    #[test]
+    fn test_register_invalid() {
+        let registry: MetaRegistry64x64<u32> = MetaRegistry64x64::new();
+        let venue = Validation { allowed: CharClasses::ALPHANUMERIC, ..Validation::default() };
+        StaticId64x64::set_validation(Validation::default(), venue);
+
+        let result = registry.register_str("AAPL", "NAS DAQ", 1);
+        assert!(matches!(result, Err(RegisterError::Invalid(IdError::DisallowedChar { ch: ' ', .. }))));
+        assert!(result.unwrap_err().to_string().contains("disallowed"));
+        assert_eq!(StaticId64x64::lookup("AAPL", "NAS DAQ"), None);
+        assert!(registry.is_empty());
+
+        let aapl = registry.register_str("AAPL", "NASDAQ", 1).unwrap();
+        assert_eq!(registry.register_str("AAPL", "NASDAQ", 2), Err(RegisterError::AlreadyRegistered(aapl)));
+    }
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::Normalization;
+
+    #[test]
+    fn test_canonical_policy() {
+        StaticId32x16::set_normalization(Normalization::CANONICAL);
+        assert_eq!(StaticId32x16::normalization(), Normalization::CANONICAL);
+
+        let id1 = StaticId32x16::from_str("aapl", "NASDAQ");
+        let id2 = StaticId32x16::from_str(" AAPL ", "nasdaq");
+        let id3 = StaticId32x16::from_bytes(b"AAPL", b" NASDAQ");
+        let id4 = StaticId32x16::from_combined_str(" aapl @ nasdaq ");
+
+        assert_eq!(id1, id2);
+        assert_eq!(id1, id3);
+        assert_eq!(id1, id4);
+        assert_eq!(id1.code_str(), "AAPL");
+        assert_eq!(id1.venue_str(), "NASDAQ");
+
+        let brk = StaticId32x16::from_str("brk  b", "NYSE");
+        assert_eq!(brk.code_str(), "BRK B");
+
+        assert_eq!(StaticId32x16::lookup("Aapl", "Nasdaq"), Some(id1));
+        assert_eq!(StaticId32x16::ids_for_code(" aapl"), vec![id1]);
+    }
+
+    #[test]
+    fn test_verbatim_is_default() {
+        assert_eq!(StaticId64x32::normalization(), Normalization::VERBATIM);
+
+        let id1 = StaticId64x32::from_str("aapl", "NASDAQ");
+        let id2 = StaticId64x32::from_str("AAPL", "NASDAQ");
+        assert_ne!(id1, id2);
+        assert_eq!(id1.code_str(), "aapl");
+    }
+
+    #[test]
+    fn test_single_rule_policy() {
+        StaticId16x0::set_normalization(Normalization {
+            trim: true,
+            ..Normalization::VERBATIM
+        });
+
+        let id1 = StaticId16x0::from_str(" btc ");
+        let id2 = StaticId16x0::from_str("btc");
+        let id3 = StaticId16x0::from_str("BTC");
+        assert_eq!(id1, id2);
+        assert_ne!(id1, id3);
+    }
+}
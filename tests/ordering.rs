@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use static_id::dynamic::DynamicId;
+    use static_id::static_id::*;
+    use static_id::Symbol;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_symbol() {
+        let (ab, abc, b) = (Symbol::<8>::from("AB"), Symbol::<8>::from("ABC"), Symbol::<8>::from("B"));
+        assert!(ab < abc);
+        assert!(abc < b);
+        assert!(Symbol::<8>::default() < ab);
+        assert_eq!(ab.cmp(&Symbol::from("AB")), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_symbol_from_raw_bytes() {
+        let raw = Symbol::<8>::from(&b"A\xFFB"[..]);
+        let text = Symbol::<8>::from("A");
+        assert_eq!(raw, text);
+        assert_eq!(raw.cmp(&text), std::cmp::Ordering::Equal);
+        assert_eq!(raw.as_raw_bytes(), text.as_raw_bytes());
+
+        let mut symbols = vec![Symbol::<8>::from("B"), raw, Symbol::from(&b"A"[..]), text, Symbol::from("AB")];
+        symbols.sort();
+        symbols.dedup();
+        assert_eq!(symbols, [text, Symbol::from("AB"), Symbol::from("B")]);
+    }
+
+    #[test]
+    fn test_lexicographic() {
+        let ids = [
+            StaticId::from_str("MSFT", "NASDAQ"),
+            StaticId::from_str("AAPL", "NYSE"),
+            StaticId::from_str("AAPL", "NASDAQ"),
+            StaticId::from_str("AAP", "NYSE"),
+        ];
+        let mut sorted = ids.to_vec();
+        sorted.sort();
+        let text: Vec<_> = sorted.iter().map(|id| id.to_string()).collect();
+        assert_eq!(text, ["AAP@NYSE", "AAPL@NASDAQ", "AAPL@NYSE", "MSFT@NASDAQ"]);
+        assert_eq!(sorted[0].get_id().cmp(sorted[1].get_id()), std::cmp::Ordering::Less);
+
+        let positions: BTreeMap<StaticId, i64> = ids.iter().map(|&id| (id, 100)).collect();
+        assert_eq!(positions.keys().next(), Some(&StaticId::from_str("AAP", "NYSE")));
+        assert_eq!(positions[&StaticId::from_str("AAPL", "NASDAQ")], 100);
+    }
+
+    #[test]
+    fn test_by_index() {
+        let z = StaticId64x64::from_str("ZZZ", "");
+        let a = StaticId64x64::from_str("AAA", "");
+        assert!(a < z);
+        assert!(ByIndex(z) < ByIndex(a));
+        assert_eq!(ByIndex::from(a), ByIndex(StaticId64x64::from_str("AAA", "")));
+        assert_eq!(format!("{:?}", ByIndex(a)), "AAA@");
+    }
+
+    #[test]
+    fn test_dynamic() {
+        let mut ids = [DynamicId::from_str("B", "X"), DynamicId::from_str("A", "Y"), DynamicId::from_str("A", "X")];
+        ids.sort();
+        let text: Vec<_> = ids.iter().map(|id| id.to_string()).collect();
+        assert_eq!(text, ["A@X", "A@Y", "B@X"]);
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+
+    #[test]
+    fn test_search_prefix() {
+        let spy = StaticId::from_str("SPY", "ARCA");
+        let spyg = StaticId::from_str("SPYG", "ARCA");
+        let spy_bats = StaticId::from_str("SPY", "BATS");
+        let _ = StaticId::from_str("QQQ", "ARCA");
+        let _ = StaticId::from_str("SP", "ARCA");
+
+        assert_eq!(StaticId::search_prefix("SPY", Some("ARCA")), vec![spy, spyg]);
+        assert_eq!(StaticId::search_prefix("SPY", None), vec![spy, spy_bats, spyg]);
+        assert!(StaticId::search_prefix("SPY", Some("NYSE")).is_empty());
+        assert!(StaticId::search_prefix("ZZZ", None).is_empty());
+    }
+
+    #[test]
+    fn test_search_glob() {
+        let esz4 = StaticId16x16::from_str("ESZ4", "CME");
+        let esh5 = StaticId16x16::from_str("ESH5", "CME");
+        let es_eurex = StaticId16x16::from_str("ESZ4", "EUREX");
+        let _ = StaticId16x16::from_str("NQZ4", "CME");
+
+        assert_eq!(StaticId16x16::search_glob("ES*@CME"), vec![esh5, esz4]);
+        assert_eq!(StaticId16x16::search_glob("ES?4"), vec![esz4, es_eurex]);
+        assert_eq!(StaticId16x16::search_glob("*Z4@EU*"), vec![es_eurex]);
+        assert!(StaticId16x16::search_glob("ES*@ICE").is_empty());
+    }
+
+    #[test]
+    fn test_search_glob_escaped_separator() {
+        let ab = StaticId16x16::from_str("A@B1", "X");
+        let _ = StaticId16x16::from_str("A@B2", "Y");
+        let _ = StaticId16x16::from_str("AB", "X");
+
+        assert_eq!(StaticId16x16::search_glob(r"A\@B*@X"), vec![ab]);
+        assert_eq!(StaticId16x16::search_glob(r"A\@B1"), vec![ab]);
+    }
+
+    #[test]
+    fn test_search_glob_without_venue() {
+        let btc = StaticId32x0::from_str("BTC-PERP");
+        let eth = StaticId32x0::from_str("ETH-PERP");
+        let _ = StaticId32x0::from_str("BTC-SPOT");
+
+        assert_eq!(StaticId32x0::search_glob("*-PERP"), vec![btc, eth]);
+    }
+}
@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+
+    #[test]
+    fn test_ids_for_venue() {
+        let samsung = StaticId::from_str("005930", "KRX");
+        let hynix = StaticId::from_str("000660", "KRX");
+        let _ = StaticId::from_str("AAPL", "NASDAQ");
+        // re-interning must not duplicate index entries
+        let _ = StaticId::from_str("005930", "KRX");
+
+        assert_eq!(StaticId::ids_for_venue("KRX"), vec![samsung, hynix]);
+        assert!(StaticId::ids_for_venue("UNKNOWN").is_empty());
+    }
+
+    #[test]
+    fn test_ids_for_code() {
+        let on_xkrx = StaticId16x16::from_str("005930", "XKRX");
+        let on_lse = StaticId16x16::from_str("005930", "LSE");
+        let _ = StaticId16x16::from_str("000660", "XKRX");
+
+        assert_eq!(StaticId16x16::ids_for_code("005930"), vec![on_xkrx, on_lse]);
+        assert!(StaticId16x16::ids_for_code("UNKNOWN").is_empty());
+    }
+
+    #[test]
+    fn test_ids_for_code_without_venue() {
+        let id = StaticId32x0::from_str("BTC");
+        assert_eq!(StaticId32x0::ids_for_code("BTC"), vec![id]);
+        assert!(StaticId32x0::ids_for_venue("").contains(&id));
+    }
+}
@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+    use static_id::static_id::*;
+    use static_id::serde_repr;
+    use static_id::SerdeRepr;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Reprs {
+        #[serde(with = "static_id::serde_repr::combined")]
+        combined: StaticId,
+        #[serde(with = "static_id::serde_repr::tuple")]
+        tuple: StaticId16x16,
+        #[serde(with = "static_id::serde_repr::structure")]
+        structure: StaticId64x64,
+        #[serde(with = "static_id::serde_repr::index")]
+        index: StaticId32x0,
+    }
+
+    #[test]
+    fn test_with_modules() {
+        let btc = StaticId32x0::from_str("BTC");
+        let reprs = Reprs {
+            combined: StaticId::from_str("AAPL", "NASDAQ"),
+            tuple: StaticId16x16::from_str("005930", "KRX"),
+            structure: StaticId64x64::from_str("ESZ4", "CME"),
+            index: btc,
+        };
+
+        let json = serde_json::to_string(&reprs).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"combined":"AAPL@NASDAQ","tuple":["005930","KRX"],"structure":{{"code":"ESZ4","venue":"CME"}},"index":{}}}"#,
+                btc.index()
+            )
+        );
+        let deserde: Reprs = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserde, reprs);
+    }
+
+    #[test]
+    fn test_index() {
+        let ids: Vec<_> = ["A", "B", "C"].iter().map(|code| StaticId64x16::from_str(code, "X")).collect();
+        for id in &ids {
+            assert_eq!(StaticId64x16::from_index(id.index()), Some(*id));
+        }
+        assert_eq!(StaticId64x16::from_index(u32::MAX), None);
+
+        #[derive(Deserialize)]
+        struct Indexed(#[serde(with = "static_id::serde_repr::index")] #[allow(dead_code)] StaticId64x16);
+        assert!(serde_json::from_str::<Indexed>("4000000000").is_err());
+    }
+
+    #[test]
+    fn test_per_type_repr() {
+        StaticId16x32::set_serde_repr(Some(SerdeRepr::Tuple));
+        assert_eq!(StaticId16x32::serde_repr(), SerdeRepr::Tuple);
+
+        let id = StaticId16x32::from_str("AAPL", "NASDAQ");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#"["AAPL","NASDAQ"]"#);
+        assert_eq!(serde_json::from_str::<StaticId16x32>(&json).unwrap(), id);
+        assert!(serde_json::from_str::<StaticId16x32>(r#""AAPL@NASDAQ""#).is_err());
+    }
+
+    #[test]
+    fn test_process_wide_default() {
+        // types with their own representation are not affected
+        StaticId32x64::set_serde_repr(Some(SerdeRepr::Combined));
+        serde_repr::set_default_repr(SerdeRepr::Struct);
+        assert_eq!(serde_repr::default_repr(), SerdeRepr::Struct);
+        assert_eq!(StaticId32x16::serde_repr(), SerdeRepr::Struct);
+
+        let id = StaticId32x16::from_str("AAPL", "NASDAQ");
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(json, r#"{"code":"AAPL","venue":"NASDAQ"}"#);
+        assert_eq!(serde_json::from_str::<StaticId32x16>(&json).unwrap(), id);
+
+        let id = StaticId32x64::from_str("AAPL", "NASDAQ");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""AAPL@NASDAQ""#);
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use static_id::shared::SharedInterner;
+    use static_id::static_id::*;
+    use static_id::{Symbol, Validation};
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("static-id-test-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn core(code: &str, venue: &str) -> IdCore16x16 {
+        IdCore16x16 { code: Symbol::from(code), venue: Symbol::from(venue) }
+    }
+
+    #[test]
+    fn test_attached_interners_agree() {
+        let path = temp_path("agree");
+        let first = SharedInterner::<16, 16>::open(&path, 64).unwrap();
+        let second = SharedInterner::<16, 16>::open(&path, 64).unwrap();
+
+        assert_eq!(first.intern(&core("AAPL", "XNAS")).unwrap(), 0);
+        assert_eq!(second.intern(&core("MSFT", "XNAS")).unwrap(), 1);
+        assert_eq!(second.intern(&core("AAPL", "XNAS")).unwrap(), 0);
+        assert_eq!(first.get(&core("MSFT", "XNAS")), Some(1));
+        assert_eq!(first.get(&core("GOOG", "XNAS")), None);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.core(1), Some(&core("MSFT", "XNAS")));
+        assert_eq!(second.core(2), None);
+        drop((first, second));
+
+        // the table outlives the processes attached to it
+        let third = SharedInterner::<16, 16>::open(&path, 1).unwrap();
+        assert_eq!(third.capacity(), 64);
+        assert_eq!(third.get(&core("MSFT", "XNAS")), Some(1));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_to_static_id() {
+        let path = temp_path("resolve");
+        let interner = SharedInterner::<32, 16>::open(&path, 8).unwrap();
+        let id = StaticId32x16::from_str("KR7005930003", "KRX");
+        let index = interner.intern_id(id).unwrap();
+        assert_eq!(interner.get_id(id), Some(index));
+        assert_eq!(interner.resolve(index), Some(id));
+        assert_eq!(interner.resolve(index + 1), None);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    const CHILD_PATH_VAR: &str = "STATIC_ID_SHARED_CHILD_PATH";
+
+    #[test]
+    fn test_other_process_agrees() {
+        if let Ok(path) = std::env::var(CHILD_PATH_VAR) {
+            // the child process: attaches to the table of its parent
+            let interner = SharedInterner::<16, 16>::open(&path, 64).unwrap();
+            assert_eq!(interner.intern(&core("MSFT", "XNAS")).unwrap(), 1);
+            assert_eq!(interner.intern(&core("TSLA", "XNAS")).unwrap(), 2);
+            assert_eq!(interner.resolve(0), Some(StaticId16x16::from_str("AAPL", "XNAS")));
+            return;
+        }
+
+        let path = temp_path("process");
+        let interner = SharedInterner::<16, 16>::open(&path, 64).unwrap();
+        assert_eq!(interner.intern(&core("AAPL", "XNAS")).unwrap(), 0);
+        assert_eq!(interner.intern(&core("MSFT", "XNAS")).unwrap(), 1);
+
+        let output = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::test_other_process_agrees", "--test-threads", "1"])
+            .env(CHILD_PATH_VAR, &path)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stdout));
+        assert_eq!(interner.get(&core("TSLA", "XNAS")), Some(2));
+        assert_eq!(interner.resolve(2), Some(StaticId16x16::from_str("TSLA", "XNAS")));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_resolve_applies_policies() {
+        let path = temp_path("policies");
+        let interner = SharedInterner::<64, 16>::open(&path, 8).unwrap();
+        let index = interner.intern(&IdCore64x16 { code: Symbol::from("a"), venue: Symbol::from("X") }).unwrap();
+        let code = Validation { min_len: 2, ..Validation::ANY };
+        StaticId64x16::set_validation(code, Validation::ANY);
+        assert_eq!(interner.resolve(index), None);
+        StaticId64x16::set_validation(Validation::ANY, Validation::ANY);
+        assert_eq!(interner.resolve(index), Some(StaticId64x16::from_str("a", "X")));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_full_and_mismatched_files() {
+        let path = temp_path("full");
+        let interner = SharedInterner::<16, 16>::open(&path, 2).unwrap();
+        interner.intern(&core("A", "")).unwrap();
+        interner.intern(&core("B", "")).unwrap();
+        assert_eq!(interner.intern(&core("C", "")).unwrap_err().kind(), ErrorKind::StorageFull);
+        assert_eq!(interner.intern(&core("B", "")).unwrap(), 1);
+
+        let err = SharedInterner::<16, 32>::open(&path, 2).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+
+        std::fs::write(&path, [0u8; 128]).unwrap();
+        let err = SharedInterner::<16, 16>::open(&path, 2).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_concurrent_inserts() {
+        let path = temp_path("concurrent");
+        SharedInterner::<16, 16>::open(&path, 1024).unwrap();
+
+        // each thread attaches on its own, like a separate process would
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let interner = SharedInterner::<16, 16>::open(&path, 1024).unwrap();
+                    (0..500)
+                        .map(|i| {
+                            let i = (i * 7 + t * 13) % 500;
+                            let code = i.to_string();
+                            (code.clone(), interner.intern(&core(&code, "X")).unwrap())
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        let interner = SharedInterner::<16, 16>::open(&path, 1024).unwrap();
+        assert_eq!(interner.len(), 500);
+        for (code, index) in results.iter().flatten() {
+            assert_eq!(interner.core(*index).unwrap().code.as_str(), code);
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::stream::{IdDecoder, IdEncoder};
+    use static_id::IdFormat;
+
+    #[test]
+    fn test_round_trip() {
+        let ids: Vec<_> = (0..10_000)
+            .map(|i| StaticId::from_str(["AAPL", "MSFT", "005930", "A@B"][i % 4], ["NASDAQ", "KRX"][i % 2]))
+            .collect();
+
+        let mut encoder = IdEncoder::new(Vec::new()).unwrap();
+        for &id in &ids {
+            encoder.encode(id).unwrap();
+        }
+        assert_eq!(encoder.dictionary_len(), 4);
+        let bytes = encoder.finish().unwrap();
+        // header, 4 definitions and one byte per repeated id
+        assert!(bytes.len() < 4 + 4 * 20 + ids.len());
+
+        let mut decoder = IdDecoder::<_, 32, 32>::new(bytes.as_slice()).unwrap();
+        for &id in &ids {
+            assert_eq!(decoder.decode().unwrap(), Some(id));
+        }
+        assert_eq!(decoder.decode().unwrap(), None);
+        assert_eq!(decoder.dictionary_len(), 4);
+    }
+
+    #[test]
+    fn test_decoder_interns() {
+        // text written by an encoder of one type, decoded as another
+        let mut encoder = IdEncoder::new(Vec::new()).unwrap();
+        encoder.encode(StaticId16x16::from_str("ESZ4", "CME")).unwrap();
+        encoder.encode(StaticId16x16::from_str("ESZ4", "CME")).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let ids: Vec<StaticId64x64> = IdDecoder::new(bytes.as_slice()).unwrap().collect::<Result<_, _>>().unwrap();
+        let expected = StaticId64x64::lookup("ESZ4", "CME").unwrap();
+        assert_eq!(ids, vec![expected, expected]);
+    }
+
+    #[test]
+    fn test_separator_is_not_special() {
+        StaticId32x16::set_format(IdFormat::new('-'));
+        let id = StaticId32x16::from_str("BTC-PERP", "A\\B");
+
+        let mut encoder = IdEncoder::new(Vec::new()).unwrap();
+        encoder.encode(id).unwrap();
+        let bytes = encoder.finish().unwrap();
+
+        let mut decoder = IdDecoder::<_, 32, 16>::new(bytes.as_slice()).unwrap();
+        assert_eq!(decoder.decode().unwrap(), Some(id));
+    }
+
+    #[test]
+    fn test_corrupt_streams() {
+        assert!(IdDecoder::<_, 32, 32>::new(&b"XXXX"[..]).is_err());
+        assert!(IdDecoder::<_, 32, 32>::new(&b"SI"[..]).is_err());
+
+        let decode_all = |bytes: &[u8]| -> std::io::Result<Vec<StaticId>> {
+            IdDecoder::new(bytes)?.collect()
+        };
+        // reference to an undefined entry
+        assert!(decode_all(b"SID1\x01").is_err());
+        // truncated definition
+        assert!(decode_all(b"SID1\x00\x04AA").is_err());
+        // absurd length
+        assert!(decode_all(b"SID1\x00\xff\xff\xff\xff\x0f").is_err());
+        // invalid UTF-8
+        assert!(decode_all(b"SID1\x00\x01\xff\x00").is_err());
+        assert_eq!(decode_all(b"SID1").unwrap(), vec![]);
+    }
+}
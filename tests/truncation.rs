@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::{IdError, IdPart, Overflow, Truncation};
+
+    const LONG_A: &str = "SPXW  241220P05000000";
+    const LONG_B: &str = "SPXW  241220P05000001";
+
+    #[test]
+    fn test_truncate_by_default() {
+        assert_eq!(StaticId16x32::truncation(), Truncation::Truncate);
+        let (a, report) = StaticId16x32::try_from_parts_reported(LONG_A, "CBOE").unwrap();
+        assert_eq!(report.code, Overflow::Truncated);
+        assert!(!report.is_exact());
+        assert_eq!(a, StaticId16x32::from_str(LONG_B, "CBOE"));
+
+        let (_, report) = StaticId16x32::try_from_parts_reported("ESZ4", "CME").unwrap();
+        assert!(report.is_exact());
+    }
+
+    #[test]
+    fn test_truncate_multibyte() {
+        // "가" is three bytes: the 16-byte bound falls inside the sixth one
+        let id = StaticId16x16::try_from_str("가가가가가가", "KRX").unwrap();
+        assert_eq!(id.code_str(), "가가가가가");
+        assert!(std::str::from_utf8(id.code_str().as_bytes()).is_ok());
+        assert_eq!(StaticId16x16::from_str("가가가가가가가", "KRX"), id);
+    }
+
+    #[test]
+    fn test_reject() {
+        StaticId16x0::set_truncation(Truncation::Reject);
+        assert!(matches!(
+            StaticId16x0::try_from_str(LONG_A),
+            Err(IdError::TooLong { part: IdPart::Code, max_len: 16, .. })
+        ));
+        assert!(StaticId16x0::try_from_str("BTC").is_ok());
+        assert_eq!(StaticId16x0::lookup(LONG_A, ""), None);
+        assert!(std::panic::catch_unwind(|| StaticId16x0::from_str(LONG_A)).is_err());
+        assert_eq!(StaticId16x0::from_str("ETH").code_str(), "ETH");
+    }
+
+    #[test]
+    fn test_hash_suffix() {
+        StaticId16x64::set_truncation(Truncation::HashSuffix);
+        let (a, report) = StaticId16x64::try_from_parts_reported(LONG_A, "CBOE").unwrap();
+        let b = StaticId16x64::from_str(LONG_B, "CBOE");
+        assert_eq!(report.code, Overflow::HashSuffixed);
+        assert_ne!(a, b);
+        assert_eq!(a.code_str().len(), 16);
+        assert_eq!(StaticId16x64::from_str(LONG_A, "CBOE"), a);
+        assert_eq!(StaticId16x64::lookup(LONG_A, "CBOE"), Some(a));
+        assert_eq!(StaticId16x64::from_str("ESZ4", "CME").code_str(), "ESZ4");
+    }
+}
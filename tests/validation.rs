@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use static_id::static_id::*;
+    use static_id::{CharClasses, IdError, IdPart, Normalization, Truncation, Validation};
+
+    fn no_separator(code: &str) -> Result<(), String> {
+        match code.contains('@') {
+            true => Err("'@' is reserved as the separator".to_string()),
+            false => Ok(()),
+        }
+    }
+
+    #[test]
+    fn test_try_from_str() {
+        let code = Validation {
+            allowed: CharClasses::PRINTABLE_ASCII,
+            min_len: 1,
+            custom: Some(no_separator),
+        };
+        let venue = Validation {
+            allowed: CharClasses::ALPHANUMERIC,
+            min_len: 2,
+            custom: None,
+        };
+        StaticId16x16::set_validation(code, venue);
+
+        let id = StaticId16x16::try_from_str("BRK.B", "NYSE").unwrap();
+        assert_eq!(id, StaticId16x16::from_str("BRK.B", "NYSE"));
+
+        assert!(matches!(
+            StaticId16x16::try_from_str("AA@PL", "NYSE"),
+            Err(IdError::Rejected { part: IdPart::Code, .. })
+        ));
+        assert!(matches!(
+            StaticId16x16::try_from_str("AAPL\u{7}", "NYSE"),
+            Err(IdError::DisallowedChar { part: IdPart::Code, ch: '\u{7}', position: 4, .. })
+        ));
+        assert!(matches!(
+            StaticId16x16::try_from_str("삼성", "KRX"),
+            Err(IdError::DisallowedChar { part: IdPart::Code, ch: '삼', position: 0, .. })
+        ));
+        assert!(matches!(
+            StaticId16x16::try_from_str("", "NYSE"),
+            Err(IdError::TooShort { part: IdPart::Code, min_len: 1, .. })
+        ));
+        assert!(matches!(
+            StaticId16x16::try_from_str("AAPL", "N"),
+            Err(IdError::TooShort { part: IdPart::Venue, min_len: 2, .. })
+        ));
+        assert!(matches!(
+            StaticId16x16::try_from_bytes(b"AAPL", b"\xff\xfe"),
+            Err(IdError::InvalidUtf8 { part: IdPart::Venue })
+        ));
+    }
+
+    #[test]
+    fn test_try_from_combined_str() {
+        let id = StaticId64x64::try_from_combined_str("AAPL@NASDAQ").unwrap();
+        assert_eq!(id, StaticId64x64::from_str("AAPL", "NASDAQ"));
+        assert_eq!(
+            StaticId64x64::try_from_combined_str("AAPL"),
+            Err(IdError::MissingSeparator {
+                combined: "AAPL".to_string(),
+                separator: '@',
+            })
+        );
+    }
+
+    #[test]
+    fn test_validation_runs_after_normalization() {
+        let trimmed = Validation {
+            allowed: CharClasses::ALPHANUMERIC,
+            min_len: 0,
+            custom: None,
+        };
+        StaticId32x0::set_normalization(Normalization::CANONICAL);
+        StaticId32x0::set_validation(trimmed, Validation::ANY);
+
+        let id = StaticId32x0::try_from_str(" btc ").unwrap();
+        assert_eq!(id.code_str(), "BTC");
+        assert!(StaticId32x0::try_from_str("btc usd").is_err());
+    }
+
+    #[test]
+    fn test_deserialize_validates() {
+        let venue = Validation {
+            allowed: CharClasses::ANY,
+            min_len: 0,
+            custom: Some(|venue| match venue {
+                "KRX" | "NYSE" => Ok(()),
+                _ => Err("unknown venue".to_string()),
+            }),
+        };
+        StaticId32x64::set_validation(Validation::ANY, venue);
+
+        let id: StaticId32x64 = serde_json::from_str("\"005930@KRX\"").unwrap();
+        assert_eq!(id.venue_str(), "KRX");
+
+        let err = serde_json::from_str::<StaticId32x64>("\"005930@KRXX\"").unwrap_err();
+        assert!(err.to_string().contains("unknown venue"));
+        assert!(serde_json::from_str::<StaticId32x64>("\"005930\"").is_err());
+    }
+
+    #[test]
+    fn test_output_is_utf8() {
+        // the 16-byte bound of the code falls inside a 2-, 3- or 4-byte character
+        let inputs = ["가나다라마바사", "aé가나다라마바", "ab€€€€€€", "😀😀😀😀😀", "abc😀😀😀😀", "ÀÀÀÀÀÀÀÀÀ"];
+        StaticId16x64::set_truncation(Truncation::HashSuffix);
+        for code in inputs {
+            let truncated = StaticId16x32::try_from_str(code, "KRX").unwrap();
+            let suffixed = StaticId16x64::try_from_str(code, "KRX").unwrap();
+            for stored in [truncated.code_str(), suffixed.code_str()] {
+                assert!(std::str::from_utf8(stored.as_bytes()).is_ok(), "{:?}", code);
+            }
+            assert!(code.starts_with(truncated.code_str()), "{:?}", code);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use serde::de::value::{BorrowedBytesDeserializer, BytesDeserializer, Error as ValueError};
+    use serde::de::IntoDeserializer;
+    use serde::Deserialize;
+    use static_id::static_id::*;
+    use static_id::Symbol;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    struct CountingAlloc;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAlloc = CountingAlloc;
+
+    fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        let before = ALLOCATIONS.with(Cell::get);
+        let value = f();
+        (value, ALLOCATIONS.with(Cell::get) - before)
+    }
+
+    #[derive(Deserialize)]
+    struct Reprs {
+        #[serde(with = "static_id::serde_repr::combined")]
+        combined: StaticId,
+        #[serde(with = "static_id::serde_repr::tuple")]
+        tuple: StaticId,
+        #[serde(with = "static_id::serde_repr::structure")]
+        structure: StaticId,
+    }
+
+    #[test]
+    fn test_borrowed_input_does_not_allocate() {
+        let id = StaticId::from_str("AAPL", "NASDAQ");
+        let json = r#"{"combined":"AAPL@NASDAQ","tuple":["AAPL","NASDAQ"],"structure":{"code":"AAPL","venue":"NASDAQ"}}"#;
+
+        let (reprs, count) = allocations(|| serde_json::from_str::<Reprs>(json).unwrap());
+        assert_eq!(count, 0);
+        assert_eq!(reprs.combined, id);
+        assert_eq!(reprs.tuple, id);
+        assert_eq!(reprs.structure, id);
+
+        let (symbol, count) = allocations(|| serde_json::from_str::<Symbol<8>>(r#""AAPL""#).unwrap());
+        assert_eq!(count, 0);
+        assert_eq!(symbol.as_str(), "AAPL");
+    }
+
+    #[test]
+    fn test_transient_input_does_not_allocate_per_id() {
+        let id = StaticId::from_str("AAPL", "NASDAQ");
+        let json = format!("[{}]", vec![r#""AAPL@NASDAQ""#; 1000].join(","));
+        let reader = std::io::Cursor::new(json.into_bytes());
+
+        // the reader's scratch buffer and the Vec allocate, but no String is built per id
+        let (ids, count) = allocations(|| serde_json::from_reader::<_, Vec<StaticId>>(reader).unwrap());
+        assert!(count < 50, "{} allocations", count);
+        assert!(ids.iter().all(|&deserde| deserde == id));
+    }
+
+    #[test]
+    fn test_bytes_input() {
+        let id = StaticId::from_str("AAPL", "NASDAQ");
+
+        let deserializer = BorrowedBytesDeserializer::<ValueError>::new(b"AAPL@NASDAQ");
+        assert_eq!(StaticId::deserialize(deserializer).unwrap(), id);
+
+        let deserializer = BytesDeserializer::<ValueError>::new(b"AAPL@NASDAQ");
+        assert_eq!(StaticId::deserialize(deserializer).unwrap(), id);
+
+        let deserializer = BytesDeserializer::<ValueError>::new(b"AAPL@\xff");
+        assert!(StaticId::deserialize(deserializer).is_err());
+
+        let deserializer = BytesDeserializer::<ValueError>::new(b"AAPL");
+        assert_eq!(Symbol::<4>::deserialize(deserializer).unwrap().as_str(), "AAPL");
+    }
+
+    #[test]
+    fn test_owned_input() {
+        let id = StaticId::from_str("AAPL", "NASDAQ");
+        let deserializer: serde::de::value::StringDeserializer<ValueError> = "AAPL@NASDAQ".to_string().into_deserializer();
+        assert_eq!(StaticId::deserialize(deserializer).unwrap(), id);
+    }
+
+    #[test]
+    fn test_errors_instead_of_panics() {
+        assert!(serde_json::from_str::<StaticId>("42").is_err());
+        assert!(serde_json::from_str::<StaticId>(r#""AAPL""#).is_err());
+        assert!(serde_json::from_str::<Symbol<2>>(r#""AAPL""#).is_err());
+
+        #[derive(Deserialize, Debug)]
+        #[allow(dead_code)]
+        struct Tuple(#[serde(with = "static_id::serde_repr::tuple")] StaticId);
+        assert!(serde_json::from_str::<Tuple>(r#"["AAPL"]"#).is_err());
+        assert!(serde_json::from_str::<Tuple>(r#"["AAPL", 1]"#).is_err());
+    }
+}